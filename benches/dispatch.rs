@@ -0,0 +1,100 @@
+//! Compares accessing heap-allocated values through `AliasPtr`, `Arc`, `Rc`, and references.
+//! See `benches/README.md` for what each group measures.
+
+use alias_ptr::AliasPtr;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::rc::Rc;
+use std::sync::Arc;
+
+const LEN: usize = 1024;
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+struct Circle(f64);
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.0 * self.0
+    }
+}
+
+fn new_shape(i: usize) -> Box<dyn Shape> {
+    if i & 1 == 0 {
+        Box::new(Square(i as f64))
+    } else {
+        Box::new(Circle(i as f64))
+    }
+}
+
+fn dyn_dispatch(c: &mut Criterion) {
+    let boxes: Vec<Box<dyn Shape>> = (0..LEN).map(new_shape).collect();
+    let refs: Vec<&dyn Shape> = boxes.iter().map(|b| &**b).collect();
+    let arcs: Vec<Arc<dyn Shape>> = (0..LEN).map(|i| Arc::from(new_shape(i))).collect();
+    let aliases: Vec<AliasPtr<dyn Shape>> = (0..LEN).map(|i| new_shape(i).into()).collect();
+
+    let mut group = c.benchmark_group("dyn_dispatch");
+    group.bench_function("ref", |b| {
+        b.iter(|| black_box(&refs).iter().map(|s| s.area()).sum::<f64>())
+    });
+    group.bench_function("arc", |b| {
+        b.iter(|| black_box(&arcs).iter().map(|s| s.area()).sum::<f64>())
+    });
+    group.bench_function("alias_ptr", |b| {
+        b.iter(|| black_box(&aliases).iter().map(|s| s.area()).sum::<f64>())
+    });
+    group.finish();
+
+    for p in aliases {
+        unsafe {
+            p.delete();
+        }
+    }
+}
+
+fn value_access(c: &mut Criterion) {
+    let rcs: Vec<Rc<u64>> = (0..LEN as u64).map(Rc::new).collect();
+    let aliases: Vec<AliasPtr<u64>> = (0..LEN as u64).map(AliasPtr::new).collect();
+
+    let mut group = c.benchmark_group("value_access");
+    group.bench_function("rc", |b| {
+        b.iter(|| black_box(&rcs).iter().map(|p| **p).sum::<u64>())
+    });
+    group.bench_function("alias_ptr", |b| {
+        b.iter(|| black_box(&aliases).iter().map(|p| **p).sum::<u64>())
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("copy_pointers");
+    group.bench_function("rc", |b| {
+        b.iter(|| black_box(&rcs).iter().map(Rc::clone).collect::<Vec<_>>())
+    });
+    group.bench_function("alias_ptr", |b| {
+        b.iter(|| {
+            black_box(&aliases)
+                .iter()
+                .map(AliasPtr::copy)
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+
+    for p in aliases {
+        unsafe {
+            p.delete();
+        }
+    }
+}
+
+criterion_group!(benches, dyn_dispatch, value_access);
+criterion_main!(benches);
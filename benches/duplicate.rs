@@ -0,0 +1,29 @@
+//! Compares allocating an independent copy of a large `Copy` target
+//! using `AliasPtr::duplicate` and `Clone::clone`.
+
+use alias_ptr::AliasPtr;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+/// Large enough that copying the value dominates allocating it.
+type Block = [u64; 512];
+
+fn duplicate(c: &mut Criterion) {
+    let block: AliasPtr<Block> = AliasPtr::new([7; 512]);
+
+    let mut group = c.benchmark_group("duplicate_block");
+    group.bench_function("duplicate", |b| {
+        b.iter(|| unsafe { black_box(AliasPtr::duplicate(black_box(&block))).delete() })
+    });
+    group.bench_function("clone", |b| {
+        b.iter(|| unsafe { black_box(AliasPtr::new(Block::clone(black_box(&block)))).delete() })
+    });
+    group.finish();
+
+    unsafe {
+        block.delete();
+    }
+}
+
+criterion_group!(benches, duplicate);
+criterion_main!(benches);
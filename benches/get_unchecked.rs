@@ -0,0 +1,37 @@
+//! Compares summing values through aliases using `Deref` and `AliasPtr::get_unchecked`.
+//!
+//! The two only differ with the `checked` feature enabled:
+//! `cargo bench --bench get_unchecked --features checked`.
+
+use alias_ptr::AliasPtr;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn get_unchecked(c: &mut Criterion) {
+    let values: Vec<_> = (0..1024u64).map(AliasPtr::new).collect();
+    let aliases: Vec<_> = values.iter().map(AliasPtr::copy).collect();
+
+    let mut group = c.benchmark_group("get_unchecked");
+    group.bench_function("deref", |b| {
+        b.iter(|| black_box(&aliases).iter().map(|p| **p).sum::<u64>())
+    });
+    group.bench_function("get_unchecked", |b| {
+        b.iter(|| {
+            black_box(&aliases)
+                .iter()
+                .map(|p| unsafe { *AliasPtr::get_unchecked(p) })
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+
+    drop(aliases);
+    for p in values {
+        unsafe {
+            p.delete();
+        }
+    }
+}
+
+criterion_group!(benches, get_unchecked);
+criterion_main!(benches);
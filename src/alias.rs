@@ -0,0 +1,81 @@
+use crate::AliasPtr;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// An alias which borrows the container owning its target,
+/// so it cannot outlive the container.
+///
+/// Owning types like [`AliasFamily`][crate::AliasFamily] and [`AliasVec`][crate::AliasVec]
+/// delete their targets when dropped, so handing out an `AliasPtr` from a safe method
+/// would let safe code dereference it afterwards. They hand out an `Alias` instead,
+/// which the borrow checker keeps from outliving the owner.
+///
+/// To store an alias without borrowing the owner (for example, across `&mut` calls),
+/// unsafely convert it with [`into_ptr`][Alias::into_ptr].
+///
+/// # Examples
+///
+/// ```compile_fail,E0505
+/// # use alias_ptr::AliasFamily;
+/// let family = AliasFamily::new(1);
+/// let alias = family.alias();
+/// drop(family);
+/// println!("{}", *alias);
+/// ```
+pub struct Alias<'a, T: ?Sized> {
+    ptr: AliasPtr<T>,
+    _owner: PhantomData<&'a T>,
+}
+
+impl<'a, T: ?Sized> Alias<'a, T> {
+    /// Wraps an alias whose target is owned by a value borrowed for `'a`.
+    ///
+    /// # Safety
+    ///
+    /// The target must not be deleted or mutably borrowed during `'a`.
+    pub(crate) unsafe fn new(ptr: AliasPtr<T>) -> Alias<'a, T> {
+        Alias {
+            ptr,
+            _owner: PhantomData,
+        }
+    }
+
+    /// Provides a raw pointer to the target.
+    pub fn as_ptr(this: &Self) -> *const T {
+        AliasPtr::as_ptr(&this.ptr)
+    }
+
+    /// Returns the underlying `AliasPtr`, which no longer borrows the owner.
+    ///
+    /// # Safety
+    ///
+    /// The returned alias must not be dereferenced after the owner is dropped,
+    /// and must not be deleted.
+    pub unsafe fn into_ptr(this: Self) -> AliasPtr<T> {
+        this.ptr
+    }
+}
+
+impl<T: ?Sized> Clone for Alias<'_, T> {
+    fn clone(&self) -> Self {
+        Alias {
+            ptr: self.ptr.copy(),
+            _owner: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for Alias<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.ptr
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Alias<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{Alias, AliasPtr};
+use std::mem::MaybeUninit;
+
+/// A fixed-capacity, append-only buffer whose elements can be aliased.
+///
+/// Unlike `Vec`, `AliasVec` never reallocates, so pushing more elements
+/// never invalidates previously created aliases.
+/// All elements are dropped and the buffer is freed when the `AliasVec` is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::{Alias, AliasVec};
+/// let mut vec = AliasVec::with_capacity(2);
+/// vec.push(1).unwrap();
+/// // Stops borrowing `vec`, so the alias can be kept across `push()`.
+/// let first = unsafe { Alias::into_ptr(vec.alias(0).unwrap()) };
+/// vec.push(2).unwrap();
+/// assert_eq!(*first, 1);
+/// assert_eq!(vec.push(3), Err(3));
+/// ```
+pub struct AliasVec<T> {
+    buf: AliasPtr<[MaybeUninit<T>]>,
+    len: usize,
+}
+
+impl<T> AliasVec<T> {
+    /// Allocates an empty buffer with room for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> AliasVec<T> {
+        AliasVec {
+            buf: AliasPtr::from(Box::new_uninit_slice(capacity)),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements pushed so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no elements have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of elements the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Appends `value` to the end of the buffer,
+    /// or returns it back if the buffer is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.len == self.capacity() {
+            return Err(value);
+        }
+        // Safety: len < capacity, and slot `len` is not aliased
+        // since aliases can only be created to initialized elements.
+        unsafe {
+            self.elem_ptr(self.len).write(value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Returns a reference to the element at `index`,
+    /// or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            // Safety: the element is initialized.
+            Some(unsafe { &*self.elem_ptr(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Creates an alias to the element at `index`, or `None` if out of bounds.
+    ///
+    /// The alias borrows the `AliasVec`. Since `push()` never moves existing elements,
+    /// an alias converted by [`Alias::into_ptr`] remains valid across later calls to it,
+    /// until the `AliasVec` is dropped.
+    pub fn alias(&self, index: usize) -> Option<Alias<'_, T>> {
+        if index < self.len {
+            // Safety: the element is initialized and lies within the buffer,
+            // which is only freed when the `AliasVec` is dropped.
+            // Elements are never mutably borrowed after being pushed.
+            Some(unsafe {
+                Alias::new(AliasPtr::__map_raw(self.buf.copy(), |p| {
+                    (p as *mut T).add(index)
+                }))
+            })
+        } else {
+            None
+        }
+    }
+
+    fn elem_ptr(&self, index: usize) -> *mut T {
+        debug_assert!(index < self.capacity());
+        // Safety: index is within the allocation.
+        unsafe { (AliasPtr::as_mut_ptr(&self.buf) as *mut T).add(index) }
+    }
+}
+
+impl<T> Drop for AliasVec<T> {
+    fn drop(&mut self) {
+        // Safety: the first `len` elements are initialized,
+        // and `buf` was created from a Box.
+        unsafe {
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                AliasPtr::as_mut_ptr(&self.buf) as *mut T,
+                self.len,
+            ));
+            self.buf.copy().delete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_survive_push() {
+        let mut vec = AliasVec::with_capacity(4);
+        vec.push(String::from("a")).unwrap();
+        let a = unsafe { Alias::into_ptr(vec.alias(0).unwrap()) };
+        vec.push(String::from("b")).unwrap();
+        let b = unsafe { Alias::into_ptr(vec.alias(1).unwrap()) };
+        vec.push(String::from("c")).unwrap();
+
+        assert_eq!(*a, "a");
+        assert_eq!(*b, "b");
+        assert_eq!(vec.get(2).unwrap(), "c");
+        assert_eq!(vec.len(), 3);
+        assert!(vec.alias(3).is_none());
+    }
+
+    #[test]
+    fn test_push_full() {
+        let mut vec = AliasVec::with_capacity(1);
+        assert_eq!(vec.push(1), Ok(()));
+        assert_eq!(vec.push(2), Err(2));
+        assert_eq!(vec.capacity(), 1);
+
+        let empty = AliasVec::<String>::with_capacity(0);
+        assert!(empty.is_empty());
+    }
+}
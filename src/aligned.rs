@@ -0,0 +1,120 @@
+use crate::{Alias, AliasPtr};
+use std::alloc::{self, Layout};
+
+/// A heap-allocated `T` with a larger alignment than `T` requires,
+/// which frees `T` when dropped.
+///
+/// This is intended for SIMD or DMA buffers. An `AliasPtr` cannot free
+/// an over-aligned allocation itself (since `delete()` frees with the layout
+/// of `T`), so the `AlignedBox` owns the allocation and remembers its layout,
+/// and hands out aliases through [`alias`][AlignedBox::alias].
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::{Alias, AlignedBox};
+/// let buf = AlignedBox::new([0u8; 16], 64);
+/// let alias = buf.alias();
+/// assert_eq!(Alias::as_ptr(&alias) as usize % 64, 0);
+/// ```
+pub struct AlignedBox<T> {
+    ptr: AliasPtr<T>,
+    layout: Layout,
+}
+
+impl<T> AlignedBox<T> {
+    /// Allocates memory aligned to at least `align` bytes,
+    /// and then places `x` into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two, or the resulting size overflows.
+    pub fn new(x: T, align: usize) -> AlignedBox<T> {
+        let layout = Layout::new::<T>()
+            .align_to(align)
+            .expect("invalid alignment");
+        let p = if layout.size() == 0 {
+            std::ptr::without_provenance_mut::<T>(layout.align())
+        } else {
+            // Safety: the layout has nonzero size.
+            let p = unsafe { alloc::alloc(layout) } as *mut T;
+            if p.is_null() {
+                alloc::handle_alloc_error(layout);
+            }
+            p
+        };
+        // Safety: `p` is non-null, aligned, and valid for writes of a `T`.
+        unsafe {
+            p.write(x);
+            AlignedBox {
+                ptr: AliasPtr::from_raw(p),
+                layout,
+            }
+        }
+    }
+
+    /// Returns the alignment of the allocation.
+    pub fn align(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &T {
+        &self.ptr
+    }
+
+    /// Creates an alias to the value, which borrows the `AlignedBox`.
+    ///
+    /// An alias converted by [`Alias::into_ptr`] must not be dereferenced after
+    /// the `AlignedBox` is dropped, and must not be deleted
+    /// (the `AlignedBox` owns the allocation).
+    pub fn alias(&self) -> Alias<'_, T> {
+        // Safety: the value is only freed when the `AlignedBox` is dropped,
+        // and is never mutably borrowed.
+        unsafe { Alias::new(self.ptr.copy()) }
+    }
+}
+
+impl<T> Drop for AlignedBox<T> {
+    fn drop(&mut self) {
+        let p = AliasPtr::as_mut_ptr(&self.ptr);
+        // Safety: `p` holds an initialized `T`,
+        // and was allocated with `self.layout` if it has nonzero size.
+        unsafe {
+            p.drop_in_place();
+            if self.layout.size() != 0 {
+                alloc::dealloc(p as *mut u8, self.layout);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_over_aligned() {
+        let buf = AlignedBox::new(7u8, 64);
+        let alias = buf.alias();
+        assert_eq!(Alias::as_ptr(&alias) as usize % 64, 0);
+        assert_eq!(*alias, 7);
+        assert_eq!(buf.align(), 64);
+
+        let s = AlignedBox::new(String::from("a"), 4096);
+        assert_eq!(s.get() as *const String as usize % 4096, 0);
+        assert_eq!(s.get(), "a");
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let unit = AlignedBox::new((), 32);
+        assert_eq!(Alias::as_ptr(&unit.alias()) as usize % 32, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid alignment")]
+    fn test_invalid_alignment() {
+        AlignedBox::new(0u32, 3);
+    }
+}
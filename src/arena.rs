@@ -0,0 +1,257 @@
+use crate::AliasPtr;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// An arena of heap-allocated values, addressed by generational handles.
+///
+/// Each [`ArenaHandle`] holds an alias to its value, along with the slot index
+/// and the slot's generation when the value was inserted. Removing a value
+/// bumps its slot's generation, so stale handles are detected and rejected
+/// by [`get`][AliasArena::get], even if the slot has since been reused.
+/// Code which already knows a handle is live can skip the check with
+/// [`get_unchecked`][AliasArena::get_unchecked], which only dereferences the alias.
+///
+/// Values are freed when removed or when the arena is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::AliasArena;
+/// let mut arena = AliasArena::new();
+/// let a = arena.insert("a");
+/// assert_eq!(arena.get(&a), Some(&"a"));
+///
+/// assert_eq!(arena.remove(&a), Some("a"));
+/// let b = arena.insert("b");
+/// assert_eq!(arena.get(&a), None);
+/// assert_eq!(arena.get(&b), Some(&"b"));
+/// ```
+pub struct AliasArena<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+}
+
+struct Slot<T> {
+    generation: u32,
+    owner: Option<AliasPtr<T>>,
+}
+
+/// A handle to a value in an [`AliasArena`].
+///
+/// Handles can be cloned freely, and outlive the value they refer to.
+/// Values are only accessed through the arena, which checks that the handle is still live.
+pub struct ArenaHandle<T> {
+    ptr: AliasPtr<T>,
+    index: u32,
+    generation: u32,
+}
+
+impl<T> Clone for ArenaHandle<T> {
+    fn clone(&self) -> Self {
+        ArenaHandle {
+            ptr: self.ptr.copy(),
+            index: self.index,
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ArenaHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaHandle")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T> ArenaHandle<T> {
+    /// Returns the index of the handle's slot.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the generation of the handle's slot when its value was inserted.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl<T> Default for AliasArena<T> {
+    fn default() -> Self {
+        AliasArena::new()
+    }
+}
+
+impl<T> AliasArena<T> {
+    /// Creates an empty arena.
+    pub fn new() -> AliasArena<T> {
+        AliasArena {
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Returns the number of values in the arena.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns `true` if the arena holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves `x` onto the heap, and returns a handle to it.
+    /// Reuses a free slot if one is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena already has `u32::MAX` slots.
+    pub fn insert(&mut self, x: T) -> ArenaHandle<T> {
+        let owner = AliasPtr::new(x);
+        let ptr = owner.copy();
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = u32::try_from(self.slots.len())
+                    .ok()
+                    .filter(|&i| i != u32::MAX)
+                    .expect("too many arena slots");
+                self.slots.push(Slot {
+                    generation: 0,
+                    owner: None,
+                });
+                index
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.owner = Some(owner);
+        ArenaHandle {
+            ptr,
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    /// Returns the slot's owner if `handle` is live and belongs to this arena.
+    fn live_owner(&self, handle: &ArenaHandle<T>) -> Option<&AliasPtr<T>> {
+        let slot = self.slots.get(handle.index as usize)?;
+        let owner = slot.owner.as_ref()?;
+        // Comparing the pointer rejects handles from other arenas
+        // which happen to share an index and generation.
+        (slot.generation == handle.generation
+            && AliasPtr::as_ptr(owner) == AliasPtr::as_ptr(&handle.ptr))
+        .then_some(owner)
+    }
+
+    /// Returns `true` if `handle` refers to a value still in this arena.
+    pub fn contains(&self, handle: &ArenaHandle<T>) -> bool {
+        self.live_owner(handle).is_some()
+    }
+
+    /// Returns a reference to the value, or `None` if it was removed
+    /// or `handle` belongs to another arena.
+    pub fn get(&self, handle: &ArenaHandle<T>) -> Option<&T> {
+        self.live_owner(handle).map(|owner| &**owner)
+    }
+
+    /// Returns a reference to the value through the handle's alias,
+    /// without checking the slot's generation.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been returned by `insert()` on this arena,
+    /// and its value must not have been removed.
+    pub unsafe fn get_unchecked<'a>(&'a self, handle: &ArenaHandle<T>) -> &'a T {
+        debug_assert!(self.contains(handle));
+        &*AliasPtr::as_ptr(&handle.ptr)
+    }
+
+    /// Removes the value from the arena and returns it,
+    /// or returns `None` if it was already removed or `handle` belongs to another arena.
+    ///
+    /// All handles to the value become stale, and its slot may be reused.
+    pub fn remove(&mut self, handle: &ArenaHandle<T>) -> Option<T> {
+        self.live_owner(handle)?;
+        let slot = &mut self.slots[handle.index as usize];
+        let owner = slot.owner.take().unwrap();
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        // Safety: `owner` was created by AliasPtr::new(). Handles only dereference
+        // their aliases through the arena, after checking the slot's generation
+        // (or after the caller promises the value was not removed).
+        Some(*unsafe { AliasPtr::into_box(owner) })
+    }
+}
+
+impl<T> Drop for AliasArena<T> {
+    fn drop(&mut self) {
+        for owner in self.slots.iter_mut().filter_map(|slot| slot.owner.take()) {
+            // Safety: `owner` was created by AliasPtr::new(), and handles
+            // can only be dereferenced through the arena.
+            unsafe {
+                owner.delete();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_insert_get() {
+        let mut arena = AliasArena::new();
+        let handles: Vec<_> = (0..3).map(|i| arena.insert(i * 10)).collect();
+        assert_eq!(arena.len(), 3);
+        for (i, handle) in handles.iter().enumerate() {
+            assert_eq!(handle.index(), i as u32);
+            assert_eq!(arena.get(handle), Some(&(i * 10)));
+            assert_eq!(unsafe { *arena.get_unchecked(&handle.clone()) }, i * 10);
+        }
+    }
+
+    #[test]
+    fn test_stale_handle() {
+        let mut arena = AliasArena::new();
+        let a = arena.insert(String::from("a"));
+        let a2 = a.clone();
+        assert_eq!(arena.remove(&a).as_deref(), Some("a"));
+        assert!(!arena.contains(&a2));
+        assert_eq!(arena.remove(&a2), None);
+
+        // The slot is reused with a new generation.
+        let b = arena.insert(String::from("b"));
+        assert_eq!((b.index(), b.generation()), (a.index(), a.generation() + 1));
+        assert_eq!(arena.get(&a), None);
+        assert_eq!(arena.get(&b).map(String::as_str), Some("b"));
+        assert_eq!(arena.len(), 1);
+    }
+
+    #[test]
+    fn test_foreign_handle() {
+        let mut first = AliasArena::new();
+        let mut second = AliasArena::new();
+        let handle = first.insert(1);
+        second.insert(2);
+        assert_eq!(second.get(&handle), None);
+        assert_eq!(second.remove(&handle), None);
+        assert_eq!(first.get(&handle), Some(&1));
+    }
+
+    #[test]
+    fn test_drop() {
+        let counter = Rc::new(());
+        {
+            let mut arena = AliasArena::new();
+            let handle = arena.insert(counter.clone());
+            arena.insert(counter.clone());
+            arena.remove(&handle);
+            assert_eq!(Rc::strong_count(&counter), 2);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
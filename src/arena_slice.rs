@@ -0,0 +1,194 @@
+use crate::{Alias, AliasPtr};
+use std::mem::MaybeUninit;
+
+/// A fixed-length slice of slots which are initialized individually, in any order.
+///
+/// This is a low-level building block for arenas constructing objects incrementally.
+/// Slots can be initialized either by value using [`init`][ArenaSlice::init],
+/// or in place through [`slot_ptr`][ArenaSlice::slot_ptr] followed by
+/// [`assume_init`][ArenaSlice::assume_init].
+/// When the `ArenaSlice` is dropped, only the initialized slots are dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::ArenaSlice;
+/// let mut arena = ArenaSlice::new(3);
+/// arena.init(2, String::from("c"));
+/// assert_eq!(arena.get(2).unwrap(), "c");
+/// assert!(arena.get(0).is_none());
+/// ```
+pub struct ArenaSlice<T> {
+    buf: AliasPtr<[MaybeUninit<T>]>,
+    initialized: Vec<bool>,
+}
+
+impl<T> ArenaSlice<T> {
+    /// Allocates `len` uninitialized slots.
+    pub fn new(len: usize) -> ArenaSlice<T> {
+        ArenaSlice {
+            buf: AliasPtr::from(Box::new_uninit_slice(len)),
+            initialized: vec![false; len],
+        }
+    }
+
+    /// Returns the number of slots.
+    pub fn len(&self) -> usize {
+        self.initialized.len()
+    }
+
+    /// Returns `true` if there are no slots.
+    pub fn is_empty(&self) -> bool {
+        self.initialized.is_empty()
+    }
+
+    /// Returns `true` if the slot at `index` has been initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn is_init(&self, index: usize) -> bool {
+        self.initialized[index]
+    }
+
+    /// Moves `value` into the slot at `index` and returns a reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds or the slot is already initialized.
+    pub fn init(&mut self, index: usize, value: T) -> &T {
+        assert!(!self.is_init(index), "slot {} already initialized", index);
+        // Safety: the slot is in bounds and uninitialized, so nothing aliases it.
+        unsafe {
+            self.slot_ptr(index).write(MaybeUninit::new(value));
+            self.assume_init(index);
+        }
+        self.get(index).unwrap()
+    }
+
+    /// Returns a pointer to the slot at `index`, for initializing it in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn slot_ptr(&mut self, index: usize) -> *mut MaybeUninit<T> {
+        assert!(index < self.len());
+        // Safety: index is within the allocation.
+        unsafe {
+            AliasPtr::as_mut_ptr(&self.buf)
+                .cast::<MaybeUninit<T>>()
+                .add(index)
+        }
+    }
+
+    /// Marks the slot at `index` as initialized, so it will be dropped
+    /// along with the `ArenaSlice`.
+    ///
+    /// # Safety
+    ///
+    /// The slot must have been fully initialized through [`slot_ptr`][ArenaSlice::slot_ptr].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub unsafe fn assume_init(&mut self, index: usize) {
+        self.initialized[index] = true;
+    }
+
+    /// Returns a reference to the slot at `index`,
+    /// or `None` if it is out of bounds or uninitialized.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if *self.initialized.get(index)? {
+            // Safety: the slot is initialized.
+            Some(unsafe { self.buf[index].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Creates an alias to the slot at `index`,
+    /// or `None` if it is out of bounds or uninitialized.
+    ///
+    /// The alias borrows the `ArenaSlice`. Initializing other slots never moves
+    /// this one, so an alias converted by [`Alias::into_ptr`] remains valid
+    /// until the `ArenaSlice` is dropped.
+    pub fn alias(&self, index: usize) -> Option<Alias<'_, T>> {
+        if *self.initialized.get(index)? {
+            // Safety: the slot is initialized and lies within the buffer,
+            // which is only freed when the `ArenaSlice` is dropped.
+            // Initialized slots are never mutably borrowed.
+            Some(unsafe {
+                Alias::new(AliasPtr::__map_raw(self.buf.copy(), |p| {
+                    (p as *mut T).add(index)
+                }))
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Drop for ArenaSlice<T> {
+    fn drop(&mut self) {
+        let base = AliasPtr::as_mut_ptr(&self.buf) as *mut T;
+        for (i, &init) in self.initialized.iter().enumerate() {
+            if init {
+                // Safety: the slot is initialized.
+                unsafe { base.add(i).drop_in_place() }
+            }
+        }
+        // Safety: `buf` was created from a Box.
+        unsafe {
+            self.buf.copy().delete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct DropCounter(Rc<Cell<i32>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_partial_init_drop() {
+        let drops = Rc::new(Cell::new(0));
+        {
+            let mut arena = ArenaSlice::new(4);
+            arena.init(3, DropCounter(drops.clone()));
+            arena.init(1, DropCounter(drops.clone()));
+            assert!(arena.is_init(1));
+            assert!(!arena.is_init(0));
+            assert!(arena.get(2).is_none());
+        }
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn test_init_in_place() {
+        let mut arena = ArenaSlice::new(2);
+        unsafe {
+            arena.slot_ptr(0).write(MaybeUninit::new(String::from("a")));
+            arena.assume_init(0);
+        }
+        let alias = arena.alias(0).unwrap();
+        assert_eq!(*alias, "a");
+        assert!(arena.alias(1).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "already initialized")]
+    fn test_double_init() {
+        let mut arena = ArenaSlice::new(1);
+        arena.init(0, 1);
+        arena.init(0, 2);
+    }
+}
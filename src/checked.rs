@@ -0,0 +1,79 @@
+//! Tracks whether an `AliasPtr`'s target has been deleted.
+//!
+//! With the `checked` feature enabled, every copy of an `AliasPtr`
+//! shares a heap-allocated liveness flag, which `delete()` clears
+//! and dereferencing checks. This turns use-after-free and double-free
+//! into panics, at the cost of making `AliasPtr` twice as large
+//! and reference-counting the flag.
+//!
+//! Without the feature, `Liveness` is a zero-sized no-op.
+
+#[cfg(feature = "checked")]
+mod imp {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    pub(crate) struct Liveness(Arc<AtomicBool>);
+
+    impl Liveness {
+        pub(crate) fn new() -> Liveness {
+            Liveness(Arc::new(AtomicBool::new(true)))
+        }
+
+        /// Panics if the target has been deleted.
+        pub(crate) fn check(&self) {
+            if !self.is_alive() {
+                panic!("dereference of deleted AliasPtr");
+            }
+        }
+
+        /// Returns `true` if the target has not been deleted.
+        pub(crate) fn is_alive(&self) -> bool {
+            self.0.load(Ordering::Acquire)
+        }
+
+        /// Marks the target as deleted, panicking if it already was.
+        pub(crate) fn kill(&self) {
+            if !self.0.swap(false, Ordering::AcqRel) {
+                panic!("delete of already-deleted AliasPtr");
+            }
+        }
+
+        /// Returns `true` if no other copies of this `AliasPtr` exist.
+        pub(crate) fn is_unique(&self) -> bool {
+            Arc::strong_count(&self.0) == 1
+        }
+    }
+}
+
+#[cfg(not(feature = "checked"))]
+mod imp {
+    #[derive(Clone)]
+    pub(crate) struct Liveness;
+
+    impl Liveness {
+        #[inline(always)]
+        pub(crate) fn new() -> Liveness {
+            Liveness
+        }
+
+        #[inline(always)]
+        pub(crate) fn check(&self) {}
+
+        #[inline(always)]
+        pub(crate) fn is_alive(&self) -> bool {
+            true
+        }
+
+        #[inline(always)]
+        pub(crate) fn kill(&self) {}
+
+        #[inline(always)]
+        pub(crate) fn is_unique(&self) -> bool {
+            true
+        }
+    }
+}
+
+pub(crate) use imp::Liveness;
@@ -0,0 +1,101 @@
+use crate::{Alias, AliasPtr};
+
+/// A heap-allocated `T` which hands out any number of aliases to it,
+/// and frees `T` when dropped.
+///
+/// This packages up the common pattern of storing an owning `AliasPtr`
+/// in a struct, handing out copies of it, and calling `delete()` in `Drop`.
+/// Aliases are handed out as [`Alias`] handles, which borrow the family,
+/// so safe code cannot dereference them after the family frees the value.
+///
+/// Creating, aliasing, and freeing an `AliasFamily` requires no `unsafe` code,
+/// so it can be used from crates with `#![forbid(unsafe_code)]`
+/// (as can [`AliasVec`][crate::AliasVec] and [`ArenaSlice`][crate::ArenaSlice]).
+/// Keeping an alias past the borrow requires the `unsafe` [`Alias::into_ptr`].
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::AliasFamily;
+/// use std::cell::Cell;
+///
+/// let family = AliasFamily::new(Cell::new(1));
+/// let a = family.alias();
+/// let b = family.alias();
+/// a.set(42);
+/// assert_eq!(b.get(), 42);
+/// ```
+pub struct AliasFamily<T> {
+    owner: AliasPtr<T>,
+}
+
+impl<T> AliasFamily<T> {
+    /// Allocates memory on the heap and then places `x` into it.
+    pub fn new(x: T) -> AliasFamily<T> {
+        AliasFamily {
+            owner: AliasPtr::new(x),
+        }
+    }
+
+    /// Creates a new alias to the shared value, which borrows the family.
+    pub fn alias(&self) -> Alias<'_, T> {
+        // Safety: the target is only deleted when the family is dropped,
+        // and is never mutably borrowed.
+        unsafe { Alias::new(self.owner.copy()) }
+    }
+
+    /// Returns a reference to the shared value.
+    pub fn get(&self) -> &T {
+        &self.owner
+    }
+}
+
+impl<T> Drop for AliasFamily<T> {
+    fn drop(&mut self) {
+        // Safety: `owner` was created by AliasPtr::new(),
+        // and aliases borrow the family, so none can be dereferenced afterwards.
+        unsafe {
+            self.owner.copy().delete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_family_aliases() {
+        let family = AliasFamily::new(Cell::new(0));
+        let aliases: Vec<_> = (0..3).map(|_| family.alias()).collect();
+        for alias in &aliases {
+            alias.set(alias.get() + 1);
+        }
+        assert_eq!(family.get().get(), 3);
+        assert!(aliases
+            .iter()
+            .all(|a| std::ptr::eq(Alias::as_ptr(a), family.get())));
+    }
+
+    #[test]
+    fn test_family_drop() {
+        struct DropCounter(Rc<Cell<i32>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        {
+            let family = AliasFamily::new(DropCounter(drops.clone()));
+            let _a = family.alias();
+            let _b = family.alias();
+            assert_eq!(drops.get(), 0);
+        }
+        assert_eq!(drops.get(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+//! A directed graph whose edges are `AliasPtr`s, as an example use of the crate.
+//!
+//! The [`Graph`] owns every node in an arena, and deletes them all when dropped.
+//! Edges are aliases into the arena, so they never dangle while the graph is alive,
+//! and traversing an edge costs a single pointer dereference
+//! (rather than an index lookup, or reference count updates with `Rc`).
+//!
+//! With the `serde-graph` feature enabled, `Graph` implements `Serialize` and `Deserialize`.
+//! Each node is written once, and edges are written as pairs of node indices,
+//! so nodes shared by several edges (or cycles) are still shared after a round trip.
+//! Serializing aliases directly would instead write each target once per alias,
+//! and deserialize them as separate allocations.
+
+use crate::AliasPtr;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// A directed graph holding a `T` in each node.
+pub struct Graph<T> {
+    nodes: Vec<AliasPtr<Node<T>>>,
+}
+
+/// Identifies a node in a [`Graph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+struct Node<T> {
+    id: NodeId,
+    value: T,
+    edges: RefCell<Vec<AliasPtr<Node<T>>>>,
+}
+
+impl<T> Graph<T> {
+    /// Creates an empty graph.
+    pub fn new() -> Graph<T> {
+        Graph { nodes: Vec::new() }
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds a node holding `value`, with no edges.
+    pub fn add_node(&mut self, value: T) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(AliasPtr::new(Node {
+            id,
+            value,
+            edges: RefCell::new(Vec::new()),
+        }));
+        id
+    }
+
+    /// Adds an edge from `from` to `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node is not in this graph.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        let to = self.nodes[to.0].copy();
+        self.nodes[from.0].edges.borrow_mut().push(to);
+    }
+
+    /// Returns the nodes that `id` has edges to, in insertion order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in this graph.
+    pub fn neighbors(&self, id: NodeId) -> Vec<NodeId> {
+        let edges = self.nodes[id.0].edges.borrow();
+        edges.iter().map(|next| next.id).collect()
+    }
+
+    /// Returns the value held in a node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in this graph.
+    pub fn get(&self, id: NodeId) -> &T {
+        &self.nodes[id.0].value
+    }
+
+    /// Returns the values of all nodes reachable from `start`, in breadth-first order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in this graph.
+    pub fn bfs(&self, start: NodeId) -> Vec<&T> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        let mut out = Vec::new();
+
+        visited[start.0] = true;
+        queue.push_back(&*self.nodes[start.0]);
+        while let Some(node) = queue.pop_front() {
+            out.push(&node.value);
+            for next in node.edges.borrow().iter() {
+                // Safety: `next` aliases a node owned by `self`,
+                // so it lives as long as `&self`.
+                let next: &Node<T> = unsafe { &*AliasPtr::as_ptr(next) };
+                if !visited[next.id.0] {
+                    visited[next.id.0] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<T> Default for Graph<T> {
+    fn default() -> Self {
+        Graph::new()
+    }
+}
+
+impl<T> Drop for Graph<T> {
+    fn drop(&mut self) {
+        // Edges are never dereferenced while dropping nodes,
+        // so the order of deletion doesn't matter.
+        for node in self.nodes.drain(..) {
+            // Safety: each node was created by AliasPtr::new(), and is deleted once.
+            unsafe {
+                node.delete();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde-graph")]
+mod serde_impl {
+    use super::{Graph, NodeId};
+    use serde::de::{Deserialize, Deserializer, Error};
+    use serde::ser::{Serialize, Serializer};
+
+    /// The serialized form of a `Graph`: node values, and edges as (from, to) indices.
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename = "Graph")]
+    struct Repr<N> {
+        nodes: Vec<N>,
+        edges: Vec<(usize, usize)>,
+    }
+
+    impl<T: Serialize> Serialize for Graph<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut edges = Vec::new();
+            for node in &self.nodes {
+                // Each alias's target records its own index,
+                // so edges are written by identity rather than by value.
+                edges.extend(
+                    node.edges
+                        .borrow()
+                        .iter()
+                        .map(|next| (node.id.0, next.id.0)),
+                );
+            }
+            let nodes = self.nodes.iter().map(|node| &node.value).collect();
+            Repr { nodes, edges }.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Graph<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = Repr::<T>::deserialize(deserializer)?;
+            let len = repr.nodes.len();
+            if let Some(&(from, to)) = repr.edges.iter().find(|&&(a, b)| a >= len || b >= len) {
+                return Err(D::Error::custom(format_args!(
+                    "edge ({}, {}) refers to a missing node",
+                    from, to
+                )));
+            }
+
+            let mut graph = Graph::new();
+            for value in repr.nodes {
+                graph.add_node(value);
+            }
+            for (from, to) in repr.edges {
+                graph.add_edge(NodeId(from), NodeId(to));
+            }
+            Ok(graph)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(0);
+        let b = graph.add_node(1);
+        let c = graph.add_node(2);
+        let d = graph.add_node(3);
+        let e = graph.add_node(4);
+        graph.add_edge(a, b);
+        graph.add_edge(a, c);
+        graph.add_edge(b, d);
+        graph.add_edge(c, d);
+        graph.add_edge(d, a);
+
+        assert_eq!(graph.bfs(a), [&0, &1, &2, &3]);
+        assert_eq!(graph.bfs(d), [&3, &0, &1, &2]);
+        assert_eq!(graph.bfs(e), [&4]);
+        assert_eq!(*graph.get(c), 2);
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(String::from("a"));
+        graph.add_edge(a, a);
+        assert_eq!(graph.bfs(a), ["a"]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde-graph")]
+    fn test_serde_diamond() {
+        let mut graph = Graph::new();
+        let a = graph.add_node(String::from("a"));
+        let b = graph.add_node(String::from("b"));
+        let c = graph.add_node(String::from("c"));
+        let d = graph.add_node(String::from("d"));
+        graph.add_edge(a, b);
+        graph.add_edge(a, c);
+        graph.add_edge(b, d);
+        graph.add_edge(c, d);
+
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(
+            json,
+            r#"{"nodes":["a","b","c","d"],"edges":[[0,1],[0,2],[1,3],[2,3]]}"#
+        );
+
+        let copy: Graph<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(copy.len(), 4);
+        assert_eq!(copy.bfs(a), ["a", "b", "c", "d"]);
+        // `b` and `c` still point to the same `d`.
+        assert_eq!(copy.neighbors(b), [d]);
+        assert_eq!(copy.neighbors(c), [d]);
+        assert!(AliasPtr::ptr_eq(
+            &copy.nodes[b.0].edges.borrow()[0],
+            &copy.nodes[c.0].edges.borrow()[0],
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde-graph")]
+    fn test_serde_invalid_edge() {
+        let json = r#"{"nodes":[1],"edges":[[0,1]]}"#;
+        let err = serde_json::from_str::<Graph<i32>>(json).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("edge (0, 1) refers to a missing node"));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::AliasPtr;
+use std::mem::ManuallyDrop;
+
+/// Deletes an `AliasPtr`'s target when dropped, including during a panic.
+///
+/// This is useful in functions with several early returns,
+/// which would otherwise need to call `delete()` on every path.
+/// Binding a guard to a local variable deletes the target when the scope exits,
+/// whether by returning normally, early, or by unwinding.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::{AliasPtr, DeleteGuard};
+/// fn parse(input: &str) -> Option<usize> {
+///     let buf = AliasPtr::new(String::from(input));
+///     // Safety: `buf` is not used after the function returns.
+///     let _guard = unsafe { DeleteGuard::new(buf.copy()) };
+///
+///     let first = buf.split(',').next()?;
+///     first.parse().ok()
+/// }
+///
+/// assert_eq!(parse("12,34"), Some(12));
+/// assert_eq!(parse("x,34"), None);
+/// ```
+pub struct DeleteGuard<T: ?Sized>(AliasPtr<T>);
+
+impl<T: ?Sized> DeleteGuard<T> {
+    /// Creates a guard which deletes `ptr` when dropped.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`delete`][AliasPtr::delete] apply
+    /// at the point the guard is dropped: no aliases of `ptr`
+    /// may be dereferenced or deleted afterwards.
+    pub unsafe fn new(ptr: AliasPtr<T>) -> DeleteGuard<T> {
+        DeleteGuard(ptr)
+    }
+
+    /// Returns the pointer without deleting it.
+    pub fn into_inner(this: Self) -> AliasPtr<T> {
+        let this = ManuallyDrop::new(this);
+        // Safety: `this` is never dropped, so the pointer is only moved out once.
+        unsafe { std::ptr::read(&this.0) }
+    }
+
+    /// Deletes the target of a guard wrapped in `ManuallyDrop`,
+    /// at a point chosen by the caller.
+    ///
+    /// Struct fields are dropped in declaration order, after the struct's own `Drop`.
+    /// If other fields dereference aliases of the guarded pointer when dropped,
+    /// wrapping the guard in `ManuallyDrop` and calling this at the end of `Drop`
+    /// frees the target only after they are done with it.
+    ///
+    /// # Safety
+    ///
+    /// `this` must not be used or dropped again afterwards,
+    /// and the same requirements as [`DeleteGuard::new`] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::{AliasPtr, DeleteGuard};
+    /// use std::mem::ManuallyDrop;
+    ///
+    /// struct Reader(AliasPtr<String>);
+    ///
+    /// impl Drop for Reader {
+    ///     fn drop(&mut self) {
+    ///         assert_eq!(*self.0, "data");
+    ///     }
+    /// }
+    ///
+    /// struct Owner {
+    ///     buf: ManuallyDrop<DeleteGuard<String>>,
+    ///     readers: Vec<Reader>,
+    /// }
+    ///
+    /// impl Drop for Owner {
+    ///     fn drop(&mut self) {
+    ///         // Drop everything that reads from `buf` first.
+    ///         self.readers.clear();
+    ///         unsafe { DeleteGuard::drop_manually(&mut self.buf) }
+    ///     }
+    /// }
+    ///
+    /// let buf = AliasPtr::new(String::from("data"));
+    /// let readers = vec![Reader(buf.copy()), Reader(buf.copy())];
+    /// let buf = ManuallyDrop::new(unsafe { DeleteGuard::new(buf) });
+    /// drop(Owner { buf, readers });
+    /// ```
+    pub unsafe fn drop_manually(this: &mut ManuallyDrop<Self>) {
+        ManuallyDrop::drop(this);
+    }
+}
+
+impl<T: ?Sized> Drop for DeleteGuard<T> {
+    fn drop(&mut self) {
+        // Safety: upheld by the caller of DeleteGuard::new().
+        unsafe {
+            self.0.copy().delete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_scope_exit() {
+        let counter = Rc::new(());
+        {
+            let x = AliasPtr::new(counter.clone());
+            let _guard = unsafe { DeleteGuard::new(x.copy()) };
+            assert_eq!(Rc::strong_count(&x), 2);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let x = AliasPtr::new(counter.clone());
+        let guard = unsafe { DeleteGuard::new(x.copy()) };
+        let x = DeleteGuard::into_inner(guard);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_scope_unwind() {
+        let counter = Rc::new(());
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let x = AliasPtr::new(counter.clone());
+            let _guard = unsafe { DeleteGuard::new(x.copy()) };
+            panic!("oops");
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_drop_manually_after_aliases() {
+        use std::cell::RefCell;
+
+        struct Reader {
+            target: AliasPtr<String>,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Drop for Reader {
+            fn drop(&mut self) {
+                self.log.borrow_mut().push((*self.target).clone());
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let buf = AliasPtr::new(String::from("a"));
+        let readers: Vec<_> = (0..2)
+            .map(|_| Reader {
+                target: buf.copy(),
+                log: log.clone(),
+            })
+            .collect();
+        let mut guard = ManuallyDrop::new(unsafe { DeleteGuard::new(buf) });
+
+        drop(readers);
+        unsafe { DeleteGuard::drop_manually(&mut guard) };
+        assert_eq!(*log.borrow(), ["a", "a"]);
+    }
+}
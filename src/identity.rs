@@ -0,0 +1,340 @@
+//! Wrappers choosing whether `AliasPtr`s are compared by address or by value.
+//!
+//! `AliasPtr` itself does not implement `Eq` or `Hash`,
+//! since both semantics are useful: comparing by address treats aliases
+//! of the same allocation as equal (and never dereferences the pointer),
+//! while comparing by value treats distinct allocations holding equal values as equal.
+//! Wrap an `AliasPtr` in [`ByAddress`] or [`ByValue`] to pick one explicitly,
+//! for example when using it as a key in a `HashMap` or `BTreeMap`,
+//! or as an element of a `BinaryHeap` (usually ordered `ByValue`).
+
+use crate::AliasPtr;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Compares, orders, and hashes an `AliasPtr` by the address of its target.
+///
+/// Only the data address is used; the metadata of fat pointers
+/// (slice lengths or trait object vtables) is ignored.
+/// This never dereferences the pointer, so it can be used on deleted `AliasPtr`s.
+#[derive(Clone)]
+pub struct ByAddress<P>(pub P);
+
+/// Compares, orders, and hashes an `AliasPtr` by the value of its target.
+///
+/// This dereferences the pointer, so it must not be used after the target is deleted.
+/// With the `checked` feature enabled, doing so panics instead of reading freed memory.
+///
+/// Comparing two pointers to the same target returns `true` (or `Ordering::Equal`)
+/// without comparing values. This changes nothing observable for acyclic data
+/// whose comparisons are reflexive, but prevents comparisons of cyclic data structures
+/// from recursing forever.
+///
+/// Unlike `Rc`, which only skips the comparison for `T: Eq`, this applies to every
+/// `T: PartialEq`, since cyclic structures often only derive `PartialEq`.
+/// This means a pointer to `f64::NAN` compares equal to itself,
+/// even though `NAN != NAN`.
+#[derive(Clone)]
+pub struct ByValue<P>(pub P);
+
+/// Formats a pointer as its address in `Debug` output, rather than its target's value.
+///
+/// The `Debug` impl of `AliasPtr` formats the target, which recurses infinitely
+/// when printing cyclic data structures. Printing pointers through
+/// `DebugAddr` instead avoids this.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::AliasPtr;
+/// use alias_ptr::identity::DebugAddr;
+///
+/// let x = AliasPtr::new(1);
+/// assert!(format!("{:?}", DebugAddr(&x)).starts_with("0x"));
+/// # unsafe { x.delete(); }
+/// ```
+pub struct DebugAddr<'a, P: ?Sized>(pub &'a P);
+
+impl<P: ?Sized + fmt::Pointer> fmt::Debug for DebugAddr<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(self.0, f)
+    }
+}
+
+impl<T: ?Sized> ByAddress<AliasPtr<T>> {
+    const fn addr(&self) -> *const () {
+        AliasPtr::as_thin_ptr(&self.0)
+    }
+}
+
+impl<T: ?Sized> PartialEq for ByAddress<AliasPtr<T>> {
+    fn eq(&self, other: &Self) -> bool {
+        AliasPtr::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: ?Sized> Eq for ByAddress<AliasPtr<T>> {}
+
+impl<T: ?Sized> PartialOrd for ByAddress<AliasPtr<T>> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for ByAddress<AliasPtr<T>> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<T: ?Sized> Hash for ByAddress<AliasPtr<T>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+impl<T: ?Sized> ByValue<AliasPtr<T>> {
+    /// Borrows both targets, checking that they are alive.
+    fn targets<'a>(&'a self, other: &'a Self) -> (&'a T, &'a T) {
+        (&*self.0, &*other.0)
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for ByValue<AliasPtr<T>> {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = self.targets(other);
+        std::ptr::eq(a, b) || a == b
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for ByValue<AliasPtr<T>> {}
+
+impl<T: ?Sized + PartialOrd> PartialOrd for ByValue<AliasPtr<T>> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let (a, b) = self.targets(other);
+        if std::ptr::eq(a, b) {
+            return Some(Ordering::Equal);
+        }
+        a.partial_cmp(b)
+    }
+}
+
+impl<T: ?Sized + Ord> Ord for ByValue<AliasPtr<T>> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = self.targets(other);
+        if std::ptr::eq(a, b) {
+            return Ordering::Equal;
+        }
+        a.cmp(b)
+    }
+}
+
+impl<T: ?Sized + Hash> Hash for ByValue<AliasPtr<T>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.0).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_by_address_and_by_value() {
+        let a = AliasPtr::new(1);
+        let b = AliasPtr::new(1);
+
+        let by_address: HashSet<_> = vec![a.copy(), a.copy(), b.copy()]
+            .into_iter()
+            .map(ByAddress)
+            .collect();
+        assert_eq!(by_address.len(), 2);
+
+        let by_value: HashSet<_> = vec![a.copy(), a.copy(), b.copy()]
+            .into_iter()
+            .map(ByValue)
+            .collect();
+        assert_eq!(by_value.len(), 1);
+
+        drop(by_address);
+        drop(by_value);
+        unsafe {
+            a.delete();
+            b.delete();
+        }
+    }
+
+    #[test]
+    fn test_by_address_dyn() {
+        use crate::alias_dyn;
+        use std::fmt::Display;
+
+        let a = AliasPtr::new(1);
+        let b = AliasPtr::new(1);
+        let set: HashSet<ByAddress<AliasPtr<dyn Display>>> = vec![
+            alias_dyn!(Display, a.copy()),
+            alias_dyn!(Display, a.copy()),
+            alias_dyn!(Display, b.copy()),
+        ]
+        .into_iter()
+        .map(ByAddress)
+        .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&ByAddress(alias_dyn!(Display, a.copy()))));
+
+        drop(set);
+        unsafe {
+            a.delete();
+            b.delete();
+        }
+    }
+
+    #[test]
+    fn test_debug_addr_cycle() {
+        use std::cell::RefCell;
+
+        struct Node {
+            name: &'static str,
+            next: RefCell<Option<AliasPtr<Node>>>,
+        }
+
+        impl fmt::Debug for Node {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct("Node")
+                    .field("name", &self.name)
+                    .field("next", &self.next.borrow().as_ref().map(DebugAddr))
+                    .finish()
+            }
+        }
+
+        let node = AliasPtr::new(Node {
+            name: "a",
+            next: RefCell::new(None),
+        });
+        *node.next.borrow_mut() = Some(node.copy());
+
+        let expected = format!("Node {{ name: \"a\", next: Some({:p}) }}", node);
+        assert_eq!(format!("{:?}", node), expected);
+        unsafe {
+            node.delete();
+        }
+    }
+
+    #[test]
+    fn test_by_value_cycle() {
+        use std::cell::RefCell;
+
+        #[derive(PartialEq)]
+        struct Node {
+            value: i32,
+            next: RefCell<Option<ByValue<AliasPtr<Node>>>>,
+        }
+
+        let new_node = || {
+            AliasPtr::new(Node {
+                value: 1,
+                next: RefCell::new(None),
+            })
+        };
+        // `a` points to itself, and `b` points to `a`.
+        let a = new_node();
+        let b = new_node();
+        *a.next.borrow_mut() = Some(ByValue(a.copy()));
+        *b.next.borrow_mut() = Some(ByValue(a.copy()));
+
+        // Comparing `a.next` to `b.next` compares `a` to itself, which would
+        // otherwise recurse forever.
+        assert!(ByValue(a.copy()) == ByValue(b.copy()));
+        b.next.borrow_mut().take();
+        assert!(ByValue(a.copy()) != ByValue(b.copy()));
+
+        a.next.borrow_mut().take();
+        unsafe {
+            a.delete();
+            b.delete();
+        }
+    }
+
+    #[test]
+    fn test_sort_shared_by_value() {
+        let nan = AliasPtr::new(f64::NAN);
+        let one = AliasPtr::new(1.0);
+        // Aliases of the same target are consistently equal, even for NaN.
+        assert_eq!(
+            ByValue(nan.copy()).partial_cmp(&ByValue(nan.copy())),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(ByValue(nan.copy()).partial_cmp(&ByValue(one.copy())), None);
+        assert!(ByValue(nan.copy()) == ByValue(nan.copy()));
+        assert!(*nan != *nan);
+
+        let values: Vec<_> = vec![2, 1, 2, 1].into_iter().map(AliasPtr::new).collect();
+        let mut sorted: Vec<_> = (0..8).map(|i| ByValue(values[i % 4].copy())).collect();
+        sorted.sort();
+        let order: Vec<_> = sorted.iter().map(|p| *p.0).collect();
+        assert_eq!(order, [1, 1, 1, 1, 2, 2, 2, 2]);
+        // The sort is stable, so aliases stay in their original relative order.
+        let addrs: Vec<_> = sorted.iter().map(|p| AliasPtr::as_ptr(&p.0)).collect();
+        let expected: Vec<_> = [1, 3, 1, 3, 0, 2, 0, 2]
+            .iter()
+            .map(|&i| AliasPtr::as_ptr(&values[i]))
+            .collect();
+        assert_eq!(addrs, expected);
+
+        drop(sorted);
+        unsafe {
+            for p in values {
+                p.delete();
+            }
+            nan.delete();
+            one.delete();
+        }
+    }
+
+    #[test]
+    fn test_binary_heap_by_value() {
+        use std::collections::BinaryHeap;
+
+        let values: Vec<_> = vec![3, 1, 4, 1, 5].into_iter().map(AliasPtr::new).collect();
+        let mut heap: BinaryHeap<_> = values.iter().map(|p| ByValue(p.copy())).collect();
+        heap.push(ByValue(values[2].copy()));
+
+        let mut popped = Vec::new();
+        while let Some(ByValue(p)) = heap.pop() {
+            popped.push(*p);
+        }
+        assert_eq!(popped, [5, 4, 4, 3, 1, 1]);
+
+        for p in values {
+            unsafe {
+                p.delete();
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "dereference of deleted AliasPtr")]
+    fn test_by_value_deleted_eq() {
+        let a = AliasPtr::new(1);
+        let b = ByValue(a.copy());
+        unsafe {
+            a.delete();
+        }
+        let _ = b == ByValue(AliasPtr::new(1));
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "dereference of deleted AliasPtr")]
+    fn test_by_value_deleted_hash() {
+        let a = AliasPtr::new(1);
+        let b = ByValue(a.copy());
+        unsafe {
+            a.delete();
+        }
+        HashSet::new().insert(b);
+    }
+}
@@ -2,8 +2,49 @@
 //! which allows safely creating multiple pointers to the same heap-allocated memory,
 //! and (unsafely) freeing the memory without reference counting overhead.
 
+mod alias;
+mod alias_vec;
+mod aligned;
+mod arena;
+mod arena_slice;
+mod checked;
+mod family;
+#[cfg(feature = "graph")]
+pub mod graph;
+mod guard;
+pub mod identity;
+mod maybe_shared;
+mod self_ref_iter;
+mod small;
+mod trace;
+mod unique;
+mod weak;
+
+pub use alias::Alias;
+pub use alias_vec::AliasVec;
+pub use aligned::AlignedBox;
+pub use arena::{AliasArena, ArenaHandle};
+pub use arena_slice::ArenaSlice;
+pub use family::AliasFamily;
+pub use guard::DeleteGuard;
+pub use maybe_shared::MaybeShared;
+pub use self_ref_iter::SelfRefIter;
+pub use small::SmallAliasBox;
+pub use unique::UniqueAlias;
+pub use weak::{AliasOwner, AliasWeak};
+
+use checked::Liveness;
+use std::alloc::Layout;
+use std::any::{Any, TypeId};
+use std::error::Error;
+use std::ffi::c_void;
+use std::fmt;
+use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::Arc;
 
 /// An untracked shared ownership pointer, pointing to heap memory manually freed.
 ///
@@ -30,6 +71,10 @@ use std::ptr::NonNull;
 /// on exactly one `AliasPtr` pointing to it,
 /// and not dereferencing it or its aliases after.
 ///
+/// Alternatively, you may never call `delete()`, leaking the `T`.
+/// Then all copies remain valid for the rest of the program,
+/// which is useful for global singletons initialized once at startup.
+///
 /// In Rust terms, `AliasPtr<T>` acts like a `&T` that allows dangling and deletion,
 /// a `Rc<T>` or `Arc<T>` with manual deletion,
 /// or like a more convenient raw pointer which is assumed to be valid.
@@ -51,24 +96,62 @@ use std::ptr::NonNull;
 /// If these bounds are inappropriate for your data structure, you can `unsafe impl Send/Sync for`
 /// your type containing `AliasPtr`.
 ///
+/// ## Checked mode
+///
+/// Enabling the `checked` feature makes all copies of an `AliasPtr` share a
+/// liveness flag, so dereferencing or deleting an `AliasPtr` whose target was already
+/// deleted panics instead of being UB. This is intended for debugging lifetime bugs,
+/// and does not change the API. It does not catch references obtained from an
+/// `AliasPtr` outliving `delete()`, or `AliasPtr`s independently created
+/// by `from_raw()` on the same pointer.
+///
 /// ## Implementation
 ///
-/// `AliasPtr<T>` has the same size as `&T`, and is interconvertible with a `Box<T>`.
+/// `AliasPtr<T>` has the same size as `&T` (unless the `checked` feature is enabled),
+/// and is interconvertible with a `Box<T>`.
+///
+/// `AliasPtr` never calls the allocator directly. Memory is allocated through `Box`,
+/// and freed by converting back into a `Box` with `Box::from_raw()`,
+/// so both go through the same `#[global_allocator]`. This is only sound
+/// if the pointer was allocated by `Box` in the same program image: a pointer
+/// allocated by a different Rust library (such as a `cdylib` loaded over FFI,
+/// with its own global allocator) must be freed by that library instead.
 ///
 /// `AliasPtr` wraps a raw pointer rather than a `&T`,
 /// because it's not legal to pass a `&` into `Box::from_raw()`,
 /// and a dangling `&` may be UB.
 /// See ["How to Dismantle an Atomic Bomb"](http://blog.pnkfx.org/blog/2021/03/25/how-to-dismantle-an-atomic-bomb/)
 /// for details.
-#[repr(transparent)]
-pub struct AliasPtr<T: ?Sized>(NonNull<T>);
+#[cfg_attr(not(feature = "checked"), repr(transparent))]
+pub struct AliasPtr<T: ?Sized>(NonNull<T>, Liveness);
 // PhantomData is not necessary to prevent leaking Box<&'stack U> variables.
 // Also read https://docs.rs/crate/ptr/0.2.2/source/src/lib.rs for reference.
 
+// Enforce that AliasPtr has the same layout as a reference, including the null niche.
+#[cfg(not(feature = "checked"))]
+const _: () = {
+    use std::mem::size_of;
+    assert!(size_of::<AliasPtr<i32>>() == size_of::<&i32>());
+    assert!(size_of::<Option<AliasPtr<i32>>>() == size_of::<&i32>());
+    assert!(size_of::<AliasPtr<[i32]>>() == size_of::<&[i32]>());
+    assert!(size_of::<Option<AliasPtr<[i32]>>>() == size_of::<&[i32]>());
+    assert!(size_of::<AliasPtr<dyn Fn()>>() == size_of::<&dyn Fn()>());
+    assert!(size_of::<Option<AliasPtr<dyn Fn()>>>() == size_of::<&dyn Fn()>());
+};
+
+// Not derived, since that would require `T: Clone`.
 impl<T: ?Sized> Clone for AliasPtr<T> {
     /// Copy the pointer without copying the underlying data.
+    #[cfg_attr(feature = "tracing", track_caller)]
     fn clone(&self) -> Self {
-        Self(self.0)
+        trace::copied(self.0);
+        Self(self.0, self.1.clone())
+    }
+
+    /// Overwrite the pointer in place, without allocating or touching either target.
+    fn clone_from(&mut self, source: &Self) {
+        self.0 = source.0;
+        self.1.clone_from(&source.1);
     }
 }
 
@@ -78,12 +161,63 @@ impl<T: ?Sized> Clone for AliasPtr<T> {
 // Not sure.
 
 impl<T: ?Sized> From<Box<T>> for AliasPtr<T> {
+    /// Takes ownership of the `Box`'s allocation, without reallocating.
+    #[cfg_attr(feature = "tracing", track_caller)]
     fn from(item: Box<T>) -> Self {
         // Safety: pointer is obtained from Box::into_raw().
         unsafe { Self::from_raw(Box::into_raw(item)) }
     }
 }
 
+impl<T, const N: usize> From<[T; N]> for AliasPtr<[T]> {
+    /// Moves the array onto the heap as a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let buf: AliasPtr<[i32]> = [1, 2, 3].into();
+    /// assert_eq!(buf.len(), 3);
+    /// # unsafe { buf.delete(); }
+    /// ```
+    fn from(array: [T; N]) -> Self {
+        AliasPtr::from(Box::new(array) as Box<[T]>)
+    }
+}
+
+impl<T> From<Vec<T>> for AliasPtr<[T]> {
+    /// Moves the vector's elements into an allocation of exactly their size,
+    /// like `Vec::into_boxed_slice()`. This reallocates if the vector has excess capacity.
+    ///
+    /// A slice `AliasPtr` has no separate capacity, so it never holds unused memory,
+    /// and needs no equivalent of `Vec::shrink_to_fit()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let mut vec = Vec::with_capacity(100);
+    /// vec.extend([1, 2, 3]);
+    /// let buf: AliasPtr<[i32]> = vec.into();
+    /// assert_eq!(*buf, [1, 2, 3]);
+    /// # unsafe { buf.delete(); }
+    /// ```
+    #[cfg_attr(feature = "tracing", track_caller)]
+    fn from(vec: Vec<T>) -> Self {
+        AliasPtr::from(vec.into_boxed_slice())
+    }
+}
+
+impl From<AliasPtr<str>> for AliasPtr<[u8]> {
+    /// Views the string's bytes as a byte slice, keeping the same allocation.
+    ///
+    /// The result can still be deleted, since `str` and `[u8]` have the same layout.
+    fn from(s: AliasPtr<str>) -> Self {
+        // Safety: a str pointer is a byte slice pointer with the same length metadata.
+        unsafe { AliasPtr::__map_raw(s, |p| p as *mut [u8]) }
+    }
+}
+
 impl<T: Sized> AliasPtr<T> {
     /// Allocates memory on the heap and then places `x` into it.
     ///
@@ -94,109 +228,2369 @@ impl<T: Sized> AliasPtr<T> {
     /// ```
     /// # use alias_ptr::AliasPtr;
     /// let five = AliasPtr::new(5);
-    /// # let mut five = five;
     /// # unsafe { five.delete(); }
     /// ```
+    #[cfg_attr(feature = "tracing", track_caller)]
     pub fn new(x: T) -> AliasPtr<T> {
         AliasPtr::from(Box::new(x))
     }
-}
 
-impl<T: ?Sized> AliasPtr<T> {
-    /// Constructs an `AliasPtr` from a raw pointer.
+    /// Allocates uninitialized memory on the heap, and calls `f` to initialize it
+    /// in place. This avoids constructing a (possibly large) temporary `T`
+    /// on the stack and copying it to the heap.
+    ///
+    /// If `f` panics, the memory is freed without dropping its contents.
     ///
     /// # Safety
     ///
-    /// `p` must be non-null and valid (its target is readable and writable).
+    /// `f` must fully initialize the `T` before returning.
     ///
-    /// In order for calling `delete()` to be sound,
-    /// `p` must be obtained from `Box::into_raw()`.
-    pub unsafe fn from_raw(p: *mut T) -> Self {
-        Self(NonNull::new_unchecked(p))
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let table = unsafe {
+    ///     AliasPtr::<[u16; 256]>::new_with(|slot| {
+    ///         let p = slot.as_mut_ptr() as *mut u16;
+    ///         for i in 0..256 {
+    ///             p.add(i).write(i as u16 * 3);
+    ///         }
+    ///     })
+    /// };
+    /// assert_eq!(table[255], 765);
+    /// # unsafe { table.delete(); }
+    /// ```
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn new_with(f: impl FnOnce(&mut MaybeUninit<T>)) -> AliasPtr<T> {
+        let mut slot = Box::new_uninit();
+        f(&mut slot);
+        AliasPtr::from(slot.assume_init())
     }
 
-    // TODO should some of these functions be turned into type-level functions
-    // to avoid clashing with Deref?
+    /// Provides the address of the data as an untyped pointer,
+    /// for passing to C code as a userdata pointer.
+    ///
+    /// This is only available for `Sized` types,
+    /// since fat pointers do not fit in a `void*`.
+    pub fn as_c_void(this: &Self) -> *const c_void {
+        Self::as_ptr(this) as *const c_void
+    }
 
-    /// Copy the pointer without copying the underlying data.
-    /// (This is equivalent to calling `clone()`.
-    /// This type doesn't implement `Copy` to ensure all copies are explicit.)
-    pub fn copy(&self) -> Self {
-        self.clone()
+    /// Reconstructs an `AliasPtr` from an untyped pointer returned by
+    /// [`as_c_void`][AliasPtr::as_c_void].
+    ///
+    /// # Safety
+    ///
+    /// `p` must point to a `T`, and the same requirements as
+    /// [`from_ptr`][AliasPtr::from_ptr] apply.
+    pub unsafe fn from_c_void(p: *const c_void) -> Self {
+        Self::from_ptr(p as *const T)
     }
 
-    /// Call the destructor of `T` and free the allocated memory.
+    /// Returns `true` if `T` is zero-sized.
+    ///
+    /// Like `Box`, `AliasPtr` never allocates or deallocates zero-sized values.
+    pub const fn is_zst() -> bool {
+        std::mem::size_of::<T>() == 0
+    }
+
+    /// Allocates a new, independent copy of the target on the heap.
+    ///
+    /// This copies the value bitwise rather than calling `Clone::clone()`.
+    pub fn duplicate(this: &Self) -> AliasPtr<T>
+    where
+        T: Copy,
+    {
+        AliasPtr::new(**this)
+    }
+
+    /// Moves the value out of an `Arc` into a new `AliasPtr`,
+    /// if the `Arc` has no other strong references.
+    /// Otherwise returns the `Arc` unchanged.
+    pub fn from_arc(arc: Arc<T>) -> Result<AliasPtr<T>, Arc<T>> {
+        Arc::try_unwrap(arc).map(AliasPtr::new)
+    }
+
+    /// Moves the value out of an `Rc` into a new `AliasPtr`,
+    /// if the `Rc` has no other strong references.
+    /// Otherwise returns the `Rc` unchanged.
+    pub fn from_rc(rc: Rc<T>) -> Result<AliasPtr<T>, Rc<T>> {
+        Rc::try_unwrap(rc).map(AliasPtr::new)
+    }
+
+    /// Reads the value from the target without moving it,
+    /// like [`std::ptr::read`].
+    ///
+    /// This is an associated function rather than a method,
+    /// to avoid clashing with methods on `T` like `RwLock::read`.
     ///
     /// # Safety
     ///
-    /// The `AliasPtr` must be derived from `Box::into_raw()` (from the global allocator).
-    /// After calling `delete()`, neither self nor aliasing pointers
-    /// can be safely dereferenced (a safe but unsound operation).
+    /// The target must not have been deleted.
+    /// If `T` is not `Copy`, the caller must ensure that the returned value and
+    /// the target are not both dropped (or otherwise used as owned values),
+    /// like with `ptr::read`.
+    pub unsafe fn read(this: &Self) -> T {
+        this.0.as_ptr().read()
+    }
+
+    /// Overwrites the target with `value` without dropping the old value,
+    /// like [`std::ptr::write`].
     ///
-    /// This method should logically take `self` by move,
-    /// but that would unfortunately prevent `drop(&mut Parent)` from calling `delete(self)`.
-    /// `Drop` only provides `&mut Parent`, which doesn't allow moving fields out.
-    /// For discussion, see ["Re-use struct fields on drop"](https://internals.rust-lang.org/t/re-use-struct-fields-on-drop-was-drop-mut-self-vs-drop-self/8594).
-    pub unsafe fn delete(&mut self) {
-        Box::from_raw(self.0.as_ptr());
+    /// # Safety
+    ///
+    /// The target must not have been deleted,
+    /// and no references to the target (including ones obtained by dereferencing
+    /// this `AliasPtr` or its aliases) may be live during the write.
+    /// The old value is leaked unless it was previously moved out using `read()`.
+    pub unsafe fn write(this: &Self, value: T) {
+        this.0.as_ptr().write(value)
     }
 
-    /// Provides a raw pointer to the data.
+    /// Overwrites the target with a clone of `source` in place,
+    /// using `T::clone_from` to reuse the target's resources where possible.
     ///
-    /// The pointer is valid until delete() is called on the `this` or any of its aliases.
-    pub fn as_ptr(this: &Self) -> *const T {
-        this.0.as_ptr()
+    /// Unlike `AliasPtr::clone_from` (which repoints `this` at another target),
+    /// this keeps the target's address, so its aliases observe the new value.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`write`][AliasPtr::write] apply.
+    /// Additionally, `source` must not be the target or part of it.
+    pub unsafe fn clone_value_from(this: &Self, source: &T)
+    where
+        T: Clone,
+    {
+        this.1.check();
+        (*this.0.as_ptr()).clone_from(source)
+    }
+
+    /// Borrows the target as a `MaybeUninit<T>`.
+    ///
+    /// This has the same validity requirements as dereferencing the `AliasPtr`.
+    pub fn as_uninit(this: &Self) -> &MaybeUninit<T> {
+        this.1.check();
+        // Safety: MaybeUninit<T> has the same layout as T.
+        unsafe { &*(this.0.as_ptr() as *const MaybeUninit<T>) }
+    }
+
+    /// Mutably borrows the target as a `MaybeUninit<T>`,
+    /// for overwriting it (or individual fields) in place without dropping the old value.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`with_mut`][AliasPtr::with_mut] apply,
+    /// for as long as the returned reference is live.
+    /// The target must be fully initialized again before it is next dereferenced
+    /// or deleted. Values overwritten through the view are leaked, not dropped.
+    pub unsafe fn as_uninit_mut(this: &mut Self) -> &mut MaybeUninit<T> {
+        this.1.check();
+        &mut *(this.0.as_ptr() as *mut MaybeUninit<T>)
+    }
+
+    /// Reinterprets the target as a `U`, reusing the same allocation.
+    ///
+    /// # Safety
+    ///
+    /// `T` and `U` must have the same size and alignment
+    /// (checked by a debug assertion), so that `delete()` deallocates
+    /// with the correct layout. The target's bytes must be a valid `U`,
+    /// as with `std::mem::transmute::<T, U>`.
+    /// Aliasing pointers remain `AliasPtr<T>`, and must not be dereferenced
+    /// unless the target is also a valid `T`.
+    pub unsafe fn transmute<U>(this: Self) -> AliasPtr<U> {
+        debug_assert_eq!(std::mem::size_of::<T>(), std::mem::size_of::<U>());
+        debug_assert_eq!(std::mem::align_of::<T>(), std::mem::align_of::<U>());
+        AliasPtr::__map_raw(this, |p| p as *mut U)
+    }
+
+    /// Creates an alias to the element `count` places after the target,
+    /// within the same array or slice allocation.
+    ///
+    /// The result is an alias of an interior element, and must not be deleted.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as `<*mut T>::add()` apply:
+    /// the result must be in bounds of the allocation the target belongs to
+    /// (or one past the end, if it is never dereferenced).
+    pub unsafe fn add(this: Self, count: usize) -> AliasPtr<T> {
+        AliasPtr::__map_raw(this, |p| p.add(count))
+    }
+
+    /// Creates an alias to the element `count` places before the target,
+    /// within the same array or slice allocation.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`AliasPtr::add`] apply.
+    pub unsafe fn sub(this: Self, count: usize) -> AliasPtr<T> {
+        AliasPtr::__map_raw(this, |p| p.sub(count))
+    }
+
+    /// Creates an alias to the element `count` places away from the target
+    /// (which may be negative), within the same array or slice allocation.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`AliasPtr::add`] apply.
+    pub unsafe fn offset(this: Self, count: isize) -> AliasPtr<T> {
+        AliasPtr::__map_raw(this, |p| p.offset(count))
     }
 }
 
-impl<T: ?Sized> Deref for AliasPtr<T> {
-    type Target = T;
-    fn deref(&self) -> &T {
-        // Safety: AliasPtr is always constructed from a Box,
-        // so can be dereferenced safely.
-        // It is the responsibility of the user to never delete() a AliasPtr
-        // then dereference it or its aliases afterwards.
-        unsafe { &*self.0.as_ptr() }
+impl<T> AliasPtr<MaybeUninit<T>> {
+    /// Allocates uninitialized memory on the heap for a `T`.
+    ///
+    /// Deleting the result frees the memory without dropping its contents,
+    /// so it can be reused for multiple values in turn (as in an object pool),
+    /// using [`write`][AliasPtr::write] and [`drop_in_place`][AliasPtr::drop_in_place].
+    pub fn new_uninit() -> AliasPtr<MaybeUninit<T>> {
+        AliasPtr::from(Box::new_uninit())
+    }
+
+    /// Runs the destructor of the contained `T`, leaving the memory allocated
+    /// but uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// The target must contain an initialized `T`, and no references to it
+    /// (including through aliases) may be live or used afterwards.
+    pub unsafe fn drop_in_place(this: &Self) {
+        std::ptr::drop_in_place(Self::as_mut_ptr(this) as *mut T);
+    }
+
+    /// Converts to an `AliasPtr<T>`, asserting that the target is initialized.
+    ///
+    /// # Safety
+    ///
+    /// The target must contain an initialized `T`.
+    pub unsafe fn assume_init(this: Self) -> AliasPtr<T> {
+        AliasPtr::__map_raw(this, |p| p as *mut T)
     }
 }
 
-unsafe impl<T: ?Sized> Send for AliasPtr<T> where T: Send + Sync {}
-unsafe impl<T: ?Sized> Sync for AliasPtr<T> where T: Send + Sync {}
+impl<T> AliasPtr<[MaybeUninit<T>]> {
+    /// Initializes the slice by copying `src` into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has a different length than the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let buf = AliasPtr::<[u8]>::new_uninit_slice(3);
+    /// let buf = AliasPtr::write_slice(buf, b"abc");
+    /// assert_eq!(*buf, *b"abc");
+    /// # unsafe { buf.delete(); }
+    /// ```
+    pub fn write_slice(this: Self, src: &[T]) -> AliasPtr<[T]>
+    where
+        T: Copy,
+    {
+        assert_eq!(this.len(), src.len(), "source slice length mismatch");
+        // Safety: the slice is in bounds and holds no references,
+        // since MaybeUninit elements cannot be read safely.
+        // Afterwards, every element is initialized.
+        unsafe {
+            let dst = Self::as_mut_ptr(&this) as *mut T;
+            std::ptr::copy_nonoverlapping(src.as_ptr(), dst, src.len());
+            AliasPtr::__map_raw(this, |p| p as *mut [T])
+        }
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::cell::Cell;
-    use std::mem::size_of;
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod> AliasPtr<T> {
+    /// Allocates memory on the heap and copies `bytes` into it as a `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes.len()` is not equal to `size_of::<T>()`.
+    pub fn from_bytes(bytes: &[u8]) -> AliasPtr<T> {
+        AliasPtr::new(bytemuck::pod_read_unaligned(bytes))
+    }
 
-    struct AliasedPair(AliasPtr<Cell<i32>>, AliasPtr<Cell<i32>>);
+    /// Views the target as its raw bytes.
+    pub fn as_bytes(this: &Self) -> &[u8] {
+        bytemuck::bytes_of(&**this)
+    }
+}
 
-    impl AliasedPair {
-        fn new(x: i32) -> AliasedPair {
-            let x = AliasPtr::new(Cell::new(x));
-            AliasedPair(x.copy(), x)
+impl<T> AliasPtr<[T]> {
+    /// Allocates a slice of `len` clones of `value` on the heap,
+    /// like `vec![value; len]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let zeros = AliasPtr::from_elem(0u8, 64);
+    /// assert_eq!(zeros.len(), 64);
+    /// # unsafe { zeros.delete(); }
+    /// ```
+    pub fn from_elem(value: T, len: usize) -> AliasPtr<[T]>
+    where
+        T: Clone,
+    {
+        AliasPtr::from(vec![value; len].into_boxed_slice())
+    }
+
+    /// Allocates a slice of `len` elements on the heap, initializing each element
+    /// in place by calling `f` with its index, like `std::array::from_fn`.
+    ///
+    /// If `f` panics, the elements initialized so far are dropped,
+    /// and the allocation is freed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let squares = AliasPtr::from_fn(5, |i| i * i);
+    /// assert_eq!(*squares, [0, 1, 4, 9, 16]);
+    /// # unsafe { squares.delete(); }
+    /// ```
+    pub fn from_fn(len: usize, mut f: impl FnMut(usize) -> T) -> AliasPtr<[T]> {
+        /// Drops the initialized prefix of `slots` if `f` panics.
+        struct Guard<'a, T> {
+            slots: &'a mut [MaybeUninit<T>],
+            init: usize,
+        }
+
+        impl<T> Drop for Guard<'_, T> {
+            fn drop(&mut self) {
+                // Safety: the first `init` slots were initialized.
+                unsafe {
+                    let init = &mut self.slots[..self.init] as *mut [MaybeUninit<T>];
+                    std::ptr::drop_in_place(init as *mut [T]);
+                }
+            }
         }
+
+        let mut buf = Box::<[T]>::new_uninit_slice(len);
+        let mut guard = Guard {
+            slots: &mut buf,
+            init: 0,
+        };
+        while guard.init < len {
+            guard.slots[guard.init].write(f(guard.init));
+            guard.init += 1;
+        }
+        std::mem::forget(guard);
+        // Safety: all `len` slots were initialized.
+        AliasPtr::from(unsafe { buf.assume_init() })
     }
 
-    impl Drop for AliasedPair {
-        fn drop(&mut self) {
+    /// Allocates an uninitialized slice of `len` elements on the heap,
+    /// like `Box::new_uninit_slice()`.
+    pub fn new_uninit_slice(len: usize) -> AliasPtr<[MaybeUninit<T>]> {
+        AliasPtr::from(Box::new_uninit_slice(len))
+    }
+
+    /// Allocates an uninitialized slice of `len` elements on the heap,
+    /// or returns an error if the allocation fails (rather than aborting).
+    ///
+    /// The slice is allocated with exactly the layout of `[T; len]`,
+    /// so no later (infallible) reallocation is needed to shrink it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// assert!(AliasPtr::<[u64]>::try_new_uninit_slice(usize::MAX).is_err());
+    /// ```
+    pub fn try_new_uninit_slice(len: usize) -> Result<AliasPtr<[MaybeUninit<T>]>, AllocError> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError(()))?;
+        let p = if layout.size() == 0 {
+            NonNull::<MaybeUninit<T>>::dangling().as_ptr()
+        } else {
+            // Safety: the layout has nonzero size.
+            let p = unsafe { std::alloc::alloc(layout) } as *mut MaybeUninit<T>;
+            if p.is_null() {
+                return Err(AllocError(()));
+            }
+            p
+        };
+        // Safety: `p` was allocated by the global allocator with the layout
+        // `Box<[MaybeUninit<T>]>` uses for `len` elements (or is dangling if that
+        // layout is empty), and MaybeUninit elements need no initialization.
+        let b = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(p, len)) };
+        Ok(AliasPtr::from(b))
+    }
+
+    /// Allocates a slice on the heap and copies the contents of `src` into it.
+    pub fn copy_from_slice(src: &[T]) -> AliasPtr<[T]>
+    where
+        T: Copy,
+    {
+        AliasPtr::from(Box::<[T]>::from(src))
+    }
+
+    /// Creates one alias to each element of the slice.
+    ///
+    /// The element aliases must not be deleted, since they do not point to
+    /// the start of the allocation. Instead, delete the slice pointer itself
+    /// once the element aliases are no longer used.
+    pub fn element_aliases(this: &Self) -> Vec<AliasPtr<T>> {
+        (0..this.len())
+            .map(|i| {
+                // Safety: i is in bounds of the slice.
+                unsafe { AliasPtr::__map_raw(this.copy(), |p| (p as *mut T).add(i)) }
+            })
+            .collect()
+    }
+
+    /// Creates an alias to the element at `index`.
+    ///
+    /// Like those returned by [`element_aliases`][AliasPtr::element_aliases],
+    /// the element alias must not be deleted, and must not be dereferenced
+    /// after the slice is deleted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get_element(this: &Self, index: usize) -> AliasPtr<T> {
+        assert!(
+            index < this.len(),
+            "index {} out of bounds for slice of length {}",
+            index,
+            this.len()
+        );
+        // Safety: index is in bounds of the slice.
+        unsafe { AliasPtr::__map_raw(this.copy(), |p| (p as *mut T).add(index)) }
+    }
+
+    /// Returns an iterator over non-overlapping aliases of `size` elements
+    /// at a time, starting at the beginning of the slice, like [`slice::chunks`].
+    /// The last chunk is shorter if `size` does not divide the slice's length.
+    ///
+    /// Like those returned by [`get_element`][AliasPtr::get_element],
+    /// the chunk aliases must not be deleted, and must not be dereferenced
+    /// after the slice is deleted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(this: &Self, size: usize) -> impl Iterator<Item = AliasPtr<[T]>> {
+        assert!(size != 0, "chunk size must be non-zero");
+        let len = this.len();
+        let ptr = this.copy();
+        (0..len).step_by(size).map(move |start| {
+            let n = size.min(len - start);
+            // Safety: start..start + n is in bounds of the slice.
+            unsafe {
+                AliasPtr::__map_raw(ptr.copy(), |p| {
+                    std::ptr::slice_from_raw_parts_mut((p as *mut T).add(start), n)
+                })
+            }
+        })
+    }
+
+    /// Returns an iterator over overlapping aliases of `size` elements,
+    /// starting at each index in turn, like [`slice::windows`].
+    /// If the slice is shorter than `size`, the iterator returns no windows.
+    ///
+    /// The same restrictions as [`chunks`][AliasPtr::chunks]
+    /// apply to the window aliases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(this: &Self, size: usize) -> impl Iterator<Item = AliasPtr<[T]>> {
+        assert!(size != 0, "window size must be non-zero");
+        let count = this.len().checked_sub(size).map_or(0, |n| n + 1);
+        let ptr = this.copy();
+        (0..count).map(move |start| {
+            // Safety: start + size <= len, so the window is in bounds of the slice.
             unsafe {
-                self.0.delete();
+                AliasPtr::__map_raw(ptr.copy(), |p| {
+                    std::ptr::slice_from_raw_parts_mut((p as *mut T).add(start), size)
+                })
             }
+        })
+    }
+
+    /// Borrows the target as a slice.
+    ///
+    /// This is equivalent to dereferencing the `AliasPtr`,
+    /// but avoids ambiguity between methods of `AliasPtr` and `[T]`.
+    pub fn as_slice(this: &Self) -> &[T] {
+        this
+    }
+
+    /// Lends the elements to a consumer as an iterator of references,
+    /// without consuming the `AliasPtr`. Unlike [`into_iter`][AliasPtr::into_iter],
+    /// the slice remains usable once the iterator is dropped.
+    pub fn lend(this: &Self) -> std::slice::Iter<'_, T> {
+        Self::as_slice(this).iter()
+    }
+
+    /// Mutably borrows the target as a slice.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`with_mut`][AliasPtr::with_mut] apply,
+    /// for as long as the returned reference is live.
+    pub unsafe fn as_mut_slice(this: &mut Self) -> &mut [T] {
+        this.1.check();
+        &mut *this.0.as_ptr()
+    }
+
+    /// Converts a slice pointer into an array pointer to the same allocation
+    /// if the slice has exactly `N` elements, or returns it unchanged otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let buf: AliasPtr<[i32]> = [1, 2, 3].into();
+    /// let buf = AliasPtr::into_array::<2>(buf).err().unwrap();
+    /// let array: AliasPtr<[i32; 3]> = AliasPtr::into_array(buf).ok().unwrap();
+    /// assert_eq!(*array, [1, 2, 3]);
+    /// # unsafe { array.delete(); }
+    /// ```
+    pub fn into_array<const N: usize>(this: Self) -> Result<AliasPtr<[T; N]>, Self> {
+        if this.len() == N {
+            // Safety: the slice has the same layout as a [T; N].
+            Ok(unsafe { AliasPtr::__map_raw(this, |p| p as *mut T as *mut [T; N]) })
+        } else {
+            Err(this)
         }
     }
 
-    #[test]
-    fn test_option_size_of() {
-        assert_eq!(size_of::<usize>(), size_of::<AliasPtr<i32>>());
-        assert_eq!(size_of::<usize>(), size_of::<Option<AliasPtr<i32>>>());
+    /// Sorts the slice in place, like `<[T]>::sort()`.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`with_mut`][AliasPtr::with_mut] apply.
+    /// In particular, no aliases (including element aliases) may be dereferenced
+    /// while sorting, since they would observe elements being moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let mut buf: AliasPtr<[i32]> = [3, 1, 2].into();
+    /// unsafe { AliasPtr::sort(&mut buf) };
+    /// assert_eq!(*buf, [1, 2, 3]);
+    /// # unsafe { buf.delete(); }
+    /// ```
+    pub unsafe fn sort(this: &mut Self)
+    where
+        T: Ord,
+    {
+        Self::with_mut(this, |slice| slice.sort())
     }
 
-    #[test]
-    fn test_aliased_pair() {
-        let pair = AliasedPair::new(1);
-        pair.0.set(42);
-        assert_eq!(pair.1.get(), 42);
+    /// Sorts the slice in place without preserving the order of equal elements,
+    /// like `<[T]>::sort_unstable()`.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`sort`][AliasPtr::sort] apply.
+    pub unsafe fn sort_unstable(this: &mut Self)
+    where
+        T: Ord,
+    {
+        Self::with_mut(this, |slice| slice.sort_unstable())
+    }
+
+    /// Reallocates the slice with `new_len` elements, truncating it
+    /// or appending `T::default()` values.
+    ///
+    /// # Safety
+    ///
+    /// **This invalidates all aliases of `this`**, since the contents
+    /// may be moved to a new allocation. The same requirements as
+    /// [`into_box`][AliasPtr::into_box] apply: no aliases may be dereferenced
+    /// or deleted afterwards, even if the length is unchanged.
+    pub unsafe fn resize(this: Self, new_len: usize) -> AliasPtr<[T]>
+    where
+        T: Default,
+    {
+        let mut vec = Self::into_box(this).into_vec();
+        vec.resize_with(new_len, T::default);
+        AliasPtr::from(vec.into_boxed_slice())
+    }
+
+    /// Converts the slice into an iterator moving out each element,
+    /// like `Vec::into_iter()`. The allocation is freed once the iterator is dropped,
+    /// along with any elements not yet yielded.
+    ///
+    /// (`AliasPtr<[T]>` does not implement `IntoIterator`,
+    /// since that would allow safe code to free the target.)
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`into_box`][AliasPtr::into_box] apply.
+    pub unsafe fn into_iter(this: Self) -> std::vec::IntoIter<T> {
+        Self::into_box(this).into_vec().into_iter()
+    }
+
+    /// Converts each element with `f`, returning a new slice of the results.
+    /// The old allocation is freed (or reused by the standard library,
+    /// if `T` and `U` have compatible layouts).
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`into_box`][AliasPtr::into_box] apply.
+    pub unsafe fn map_elements<U, F>(this: Self, f: F) -> AliasPtr<[U]>
+    where
+        F: FnMut(T) -> U,
+    {
+        let vec: Vec<U> = Self::into_iter(this).map(f).collect();
+        AliasPtr::from(vec.into_boxed_slice())
+    }
+}
+
+impl<T: ?Sized> AliasPtr<T> {
+    /// Constructs an `AliasPtr` from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `p` must be non-null and valid (its target is readable and writable).
+    ///
+    /// In order for calling `delete()` to be sound,
+    /// `p` must be obtained from `Box::into_raw()` under the same global allocator.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn from_raw(p: *mut T) -> Self {
+        Self::from_non_null(NonNull::new_unchecked(p))
+    }
+
+    /// Constructs an `AliasPtr` from a raw pointer, or returns `None` if it is null.
+    ///
+    /// (`AliasPtr` does not implement `TryFrom<*mut T>`,
+    /// since that would allow safe code to dereference arbitrary pointers.)
+    ///
+    /// # Safety
+    ///
+    /// If `p` is non-null, the same requirements as
+    /// [`from_raw`][AliasPtr::from_raw] apply.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn try_from_raw(p: *mut T) -> Option<Self> {
+        if p.is_null() {
+            None
+        } else {
+            Some(Self::from_raw(p))
+        }
+    }
+
+    /// Constructs an `AliasPtr` from a `NonNull` pointer.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`from_raw`][AliasPtr::from_raw] apply.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn from_non_null(p: NonNull<T>) -> Self {
+        trace::created(p);
+        Self(p, Liveness::new())
+    }
+
+    /// Constructs an `AliasPtr` from a raw `*const` pointer,
+    /// such as one received through an FFI signature.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`from_raw`][AliasPtr::from_raw] apply.
+    /// Since `p` is a `*const`, you must only mutate the target
+    /// through interior-mutability types such as `Cell` or `UnsafeCell`.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn from_ptr(p: *const T) -> Self {
+        Self::from_raw(p as *mut T)
+    }
+
+    /// Constructs an `AliasPtr` to static data, such as a string literal
+    /// or the output of `include_bytes!`.
+    ///
+    /// This lets static and heap-allocated data be referred to by the same type.
+    /// The result must never be deleted, since it was not allocated by a `Box`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// static GREETING: &[u8] = b"hello";
+    /// let bytes: AliasPtr<[u8]> = AliasPtr::from_static(GREETING);
+    /// assert_eq!(&*bytes, b"hello");
+    /// ```
+    pub fn from_static(r: &'static T) -> Self {
+        // Safety: `r` is valid for the rest of the program, and only readable.
+        unsafe { Self::from_ptr(r) }
+    }
+
+    // TODO should some of these functions be turned into type-level functions
+    // to avoid clashing with Deref?
+
+    /// Copy the pointer without copying the underlying data.
+    /// (This is equivalent to calling `clone()`.
+    /// This type doesn't implement `Copy` to ensure all copies are explicit.)
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub fn copy(&self) -> Self {
+        self.clone()
+    }
+
+    /// Copies the pointer, or returns an error if the target was deleted
+    /// (or is currently being deleted).
+    ///
+    /// `delete()` marks the target as deleted before running its destructor,
+    /// so this catches re-entrant attempts to create aliases from within
+    /// the target's own `Drop` impl. Detecting deletion requires the `checked` feature.
+    /// Without it, this always succeeds.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub fn try_alias(this: &Self) -> Result<Self, AliasError> {
+        if this.1.is_alive() {
+            Ok(this.copy())
+        } else {
+            Err(AliasError(()))
+        }
+    }
+
+    /// Calls `f` with a reference to the target, then returns `self`,
+    /// for use in method chains.
+    ///
+    /// This shadows any `inspect` method on `T`,
+    /// which can still be called as `(*ptr).inspect(...)`.
+    pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+        f(&self);
+        self
+    }
+
+    /// Pushes a copy of the pointer into `out`, then returns `self`,
+    /// for use in method chains.
+    pub fn alias_to(self, out: &mut Vec<AliasPtr<T>>) -> Self {
+        out.push(self.copy());
+        self
+    }
+
+    /// Calls `f` with a reference to the target, and returns its result.
+    ///
+    /// This is convenient for chaining on optional pointers,
+    /// since `Option<AliasPtr<T>>` has the same size as `AliasPtr<T>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use alias_ptr::AliasPtr;
+    /// let slot = Some(AliasPtr::new(String::from("abc")));
+    /// let len = slot.as_ref().map(|p| AliasPtr::map_pointee(p, String::len));
+    /// assert_eq!(len, Some(3));
+    /// # unsafe { slot.unwrap().delete(); }
+    /// ```
+    pub fn map_pointee<R>(this: &Self, f: impl FnOnce(&T) -> R) -> R {
+        f(this)
+    }
+
+    /// Borrows the target, like `Deref`.
+    ///
+    /// Calling methods through `AliasPtr::get(&p).method()` always resolves
+    /// to the target's methods, even if `T` has methods named like
+    /// `AliasPtr`'s own (for example `deref` or `copy`). Binding the result
+    /// also avoids repeated liveness checks when the `checked` feature is enabled.
+    pub fn get(this: &Self) -> &T {
+        this
+    }
+
+    /// Borrows the target, without checking whether it was deleted.
+    ///
+    /// With the `checked` feature enabled, this skips the liveness check
+    /// `Deref` performs on every access (an extra load and a branch).
+    /// To measure the difference, run `cargo bench --bench get_unchecked --features checked`.
+    /// Without the feature, `Deref` performs no checks,
+    /// and this compiles to identical code. No further optimizer hints are needed,
+    /// since `NonNull` and `&T` already tell the compiler the pointer is non-null,
+    /// aligned, and dereferenceable.
+    ///
+    /// # Safety
+    ///
+    /// The target must not have been deleted, and must not be deleted
+    /// while the returned reference is live.
+    pub unsafe fn get_unchecked(this: &Self) -> &T {
+        &*this.0.as_ptr()
+    }
+
+    /// Borrows the target, or returns `None` if it was deleted.
+    ///
+    /// Detecting deletion requires the `checked` feature.
+    /// Without it, this always returns `Some`, so (like dereferencing)
+    /// it must not be called after deletion.
+    pub fn try_deref(this: &Self) -> Option<&T> {
+        if this.1.is_alive() {
+            // Safety: same as Deref.
+            Some(unsafe { &*this.0.as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Call the destructor of `T` and free the allocated memory.
+    ///
+    /// # Safety
+    ///
+    /// The `AliasPtr` must be derived from `Box::into_raw()` (from the global allocator).
+    /// After calling `delete()`, aliasing pointers
+    /// can no longer be safely dereferenced (a safe but unsound operation).
+    ///
+    /// This method takes `self` by move, so the compiler rejects
+    /// using the same handle after deleting it:
+    ///
+    /// ```compile_fail,E0382
+    /// # use alias_ptr::AliasPtr;
+    /// let x = AliasPtr::new(1);
+    /// unsafe { x.delete(); }
+    /// println!("{}", *x);
+    /// ```
+    ///
+    /// `Drop` only provides `&mut Parent`, which doesn't allow moving fields out.
+    /// To delete a field from `drop()`, delete a copy of it instead:
+    /// `unsafe { self.ptr.copy().delete(); }`.
+    /// For discussion, see ["Re-use struct fields on drop"](https://internals.rust-lang.org/t/re-use-struct-fields-on-drop-was-drop-mut-self-vs-drop-self/8594).
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn delete(self) {
+        drop(AliasPtr::into_box(self));
+    }
+
+    /// Converts the `AliasPtr` back into a `Box`, which takes ownership of the target.
+    ///
+    /// # Safety
+    ///
+    /// The `AliasPtr` must be derived from `Box::into_raw()` (from the global allocator).
+    /// Like with `delete()`, aliasing pointers can no longer be safely dereferenced
+    /// afterwards, since the `Box` may free or mutate the target.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub unsafe fn into_box(this: Self) -> Box<T> {
+        this.1.kill();
+        trace::deleted(this.0);
+        Box::from_raw(this.0.as_ptr())
+    }
+
+    /// Consumes the `AliasPtr`, returning the raw pointer along with the `Layout`
+    /// of its target, for deallocating it manually (for example, from a
+    /// deallocation callback passed to C code).
+    ///
+    /// To free the target, call `drop_in_place()` on the pointer if needed,
+    /// then `std::alloc::dealloc()` with the layout, under the same requirements
+    /// as [`delete`][AliasPtr::delete]. If `layout.size()` is zero,
+    /// nothing was allocated, and the pointer must not be passed to `dealloc()`.
+    ///
+    /// Like [`into_box`][AliasPtr::into_box], this hands ownership of the target
+    /// to the caller, so aliases must not be dereferenced afterwards.
+    /// With the `checked` feature enabled, doing so panics,
+    /// even before the target is freed.
+    #[cfg_attr(feature = "tracing", track_caller)]
+    pub fn into_raw_with_layout(this: Self) -> (*mut T, Layout) {
+        let layout = Layout::for_value::<T>(&this);
+        this.1.kill();
+        trace::deleted(this.0);
+        (this.0.as_ptr(), layout)
+    }
+
+    /// Moves the target out of the heap and frees the allocation,
+    /// or returns `this` unchanged if other copies of it are known to exist.
+    ///
+    /// Copies are only tracked with the `checked` feature enabled,
+    /// where this works like `Rc::try_unwrap`.
+    /// Without it, this always succeeds.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`into_box`][AliasPtr::into_box] apply.
+    pub unsafe fn try_into_inner(this: Self) -> Result<T, Self>
+    where
+        T: Sized,
+    {
+        if this.1.is_unique() {
+            Ok(*Self::into_box(this))
+        } else {
+            Err(this)
+        }
+    }
+
+    /// Asserts that no other copies of `this` exist, before an operation
+    /// (like [`into_box`][AliasPtr::into_box] or [`with_mut`][AliasPtr::with_mut])
+    /// which requires that no aliases are dereferenced afterwards.
+    ///
+    /// With the `checked` feature enabled, this panics if other copies still exist.
+    /// Without it, this is a no-op.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that no other copies of `this` will be dereferenced
+    /// while the following operation's requirements apply. This is usually satisfied
+    /// by ensuring no other copies exist. Without the `checked` feature,
+    /// nothing verifies this.
+    #[track_caller]
+    pub unsafe fn assume_unique(this: &Self) {
+        if !this.1.is_unique() {
+            panic!("AliasPtr assumed unique, but other copies exist");
+        }
+    }
+
+    /// Moves the target into a new reference-counted `Arc`.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`into_box`][AliasPtr::into_box] apply.
+    /// In particular, aliasing pointers can no longer be dereferenced,
+    /// since the value is moved to a new allocation.
+    pub unsafe fn into_arc(this: Self) -> Arc<T> {
+        Arc::from(Self::into_box(this))
+    }
+
+    /// Moves the target into a new reference-counted `Rc`.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`into_arc`][AliasPtr::into_arc] apply.
+    pub unsafe fn into_rc(this: Self) -> Rc<T> {
+        Rc::from(Self::into_box(this))
+    }
+
+    /// Converts the `AliasPtr` into a `Pin<Box<T>>`, for APIs requiring pinned boxes.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`into_box`][AliasPtr::into_box] apply.
+    pub unsafe fn into_pin_box(this: Self) -> std::pin::Pin<Box<T>> {
+        Box::into_pin(Self::into_box(this))
+    }
+
+    /// Calls `f` with a mutable reference to the target.
+    ///
+    /// Taking `&mut Self` prevents other uses of this handle during `f`,
+    /// but not of its aliases.
+    ///
+    /// # Safety
+    ///
+    /// The target must not have been deleted,
+    /// and no other references to the target may be live or created
+    /// (including by dereferencing aliases) until `f` returns.
+    pub unsafe fn with_mut<R>(this: &mut Self, f: impl FnOnce(&mut T) -> R) -> R {
+        this.1.check();
+        f(&mut *this.0.as_ptr())
+    }
+
+    /// Converts the pointer type, keeping the same target.
+    /// Used by [`alias_dyn!`] to perform unsizing coercions.
+    ///
+    /// # Safety
+    ///
+    /// `f` must return a non-null pointer to the same allocation as its argument.
+    #[doc(hidden)]
+    pub unsafe fn __map_raw<U: ?Sized>(
+        this: Self,
+        f: impl FnOnce(*mut T) -> *mut U,
+    ) -> AliasPtr<U> {
+        AliasPtr(NonNull::new_unchecked(f(this.0.as_ptr())), this.1)
+    }
+
+    /// Provides a raw pointer to the data.
+    ///
+    /// The pointer is valid until delete() is called on the `this` or any of its aliases.
+    pub const fn as_ptr(this: &Self) -> *const T {
+        this.0.as_ptr()
+    }
+
+    /// Provides a raw `*mut` pointer to the data.
+    ///
+    /// The pointer is valid until delete() is called on the `this` or any of its aliases.
+    /// Writing through it is only sound if no references to the target are live,
+    /// or for data inside an `UnsafeCell`.
+    pub const fn as_mut_ptr(this: &Self) -> *mut T {
+        this.0.as_ptr()
+    }
+
+    /// Provides the address of the data, discarding any metadata
+    /// (slice lengths or trait object vtables).
+    ///
+    /// This is useful for comparing against untyped pointers,
+    /// such as a `void*` received over FFI, using `std::ptr::eq`.
+    pub const fn as_thin_ptr(this: &Self) -> *const () {
+        this.0.as_ptr() as *const ()
+    }
+
+    /// Returns `true` if both pointers have the same target address,
+    /// ignoring metadata (like `Rc::ptr_eq`). This never dereferences either pointer.
+    ///
+    /// Unlike the address accessors ([`as_ptr`][AliasPtr::as_ptr],
+    /// [`as_thin_ptr`][AliasPtr::as_thin_ptr], and so on), this is not a `const fn`,
+    /// since comparing pointers (or casting them to integers) is not allowed
+    /// in const evaluation. The accessors can be called from other `const fn`s,
+    /// but since an `AliasPtr` cannot be created during const evaluation,
+    /// they can't be evaluated in a `const` item or block.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Self::as_thin_ptr(this) == Self::as_thin_ptr(other)
+    }
+
+    /// Provides a `NonNull` pointer to the data.
+    ///
+    /// The same caveats as [`as_mut_ptr`][AliasPtr::as_mut_ptr] apply.
+    pub const fn as_non_null(this: &Self) -> NonNull<T> {
+        this.0
+    }
+}
+
+/// Formats the target's value.
+///
+/// In cyclic data structures, this recurses infinitely.
+/// Use [`identity::DebugAddr`] to print addresses instead.
+impl<T: ?Sized + fmt::Debug> fmt::Debug for AliasPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Formats the target's value, like `Box`.
+impl<T: ?Sized + fmt::Display> fmt::Display for AliasPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// Forwards to the target's `Error` impl, like `Box`.
+///
+/// Converting an `AliasPtr` into a `Box<dyn Error>` (as `?` does) boxes the pointer,
+/// not the target, so the target is not deleted when the box is dropped.
+impl<T: ?Sized + Error> Error for AliasPtr<T> {
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    #[allow(deprecated)]
+    fn cause(&self) -> Option<&dyn Error> {
+        (**self).cause()
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        (**self).source()
+    }
+}
+
+/// Traces the target's `Gc` pointers, so `gc-arena` garbage collection
+/// treats objects reachable through an alias as live.
+///
+/// This does not trace the `AliasPtr`'s own allocation, which is not managed by the arena.
+/// Like dereferencing, tracing an `AliasPtr` whose target was deleted is unsound,
+/// so the target must outlive every arena object holding an alias.
+#[cfg(feature = "gc-arena")]
+unsafe impl<'gc, T: ?Sized + gc_arena::Collect<'gc>> gc_arena::Collect<'gc> for AliasPtr<T> {
+    const NEEDS_TRACE: bool = T::NEEDS_TRACE;
+
+    fn trace<C: gc_arena::collect::Trace<'gc>>(&self, cc: &mut C) {
+        cc.trace(&**self);
+    }
+}
+
+/// Formats the target's address. This never dereferences the pointer.
+impl<T: ?Sized> fmt::Pointer for AliasPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&AliasPtr::as_ptr(self), f)
+    }
+}
+
+/// The error returned by [`AliasPtr::try_alias`] when the target was deleted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AliasError(());
+
+impl fmt::Display for AliasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("alias of deleted AliasPtr")
+    }
+}
+
+impl Error for AliasError {}
+
+/// The error returned by [`AliasPtr::try_new_uninit_slice`]
+/// when the allocation fails or its size overflows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError(());
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl Error for AllocError {}
+
+impl AliasPtr<dyn Any> {
+    /// Returns the `TypeId` of the concrete type of the target.
+    ///
+    /// Calling `.type_id()` as a method on an `AliasPtr<dyn Any>` would instead
+    /// return the `TypeId` of `AliasPtr<dyn Any>` itself.
+    pub fn type_id(this: &Self) -> TypeId {
+        (**this).type_id()
+    }
+}
+
+impl<T: ?Sized> Deref for AliasPtr<T> {
+    type Target = T;
+
+    /// Borrows the target.
+    ///
+    /// This is safe but unsound if `delete()` has been called on
+    /// this `AliasPtr` or any of its aliases. The types and the borrow checker
+    /// cannot prevent this in general, since aliases are tracked independently.
+    /// (The `checked` feature turns this case into a panic.)
+    ///
+    /// The returned reference borrows from this particular handle though,
+    /// so it cannot be held across a `delete()` of the same handle:
+    ///
+    /// ```compile_fail,E0505
+    /// # use alias_ptr::AliasPtr;
+    /// let x = AliasPtr::new(1);
+    /// let r: &i32 = &x;
+    /// unsafe { x.delete(); }
+    /// println!("{}", r);
+    /// ```
+    ///
+    /// The reference remains valid as long as no alias is deleted
+    /// while the reference is live, and (like any `&T`) the target is not mutated
+    /// except through `UnsafeCell`.
+    ///
+    /// Like `Box`, the target is stored on the heap, so moving an `AliasPtr`
+    /// never changes the address `deref()` returns (the "stable deref" property
+    /// relied upon by crates like `owning_ref` and `yoke`).
+    fn deref(&self) -> &T {
+        // Safety: AliasPtr is always constructed from a Box,
+        // so can be dereferenced safely.
+        // It is the responsibility of the user to never delete() a AliasPtr
+        // then dereference it or its aliases afterwards.
+        self.1.check();
+        unsafe { &*self.0.as_ptr() }
+    }
+}
+
+/// Converts an `AliasPtr<T>` into an `AliasPtr<dyn Trait>`, where `T: Trait`.
+/// `T` may also be a trait object of a subtrait of `Trait` (trait upcasting).
+///
+/// `AliasPtr` cannot implement `CoerceUnsized` on stable Rust,
+/// so it does not coerce to trait objects automatically like `Box` does.
+/// Until `CoerceUnsized` is stabilized, this macro performs the coercion
+/// for a specific trait instead.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::{alias_dyn, AliasPtr};
+/// use std::fmt::Display;
+///
+/// let x: AliasPtr<dyn Display> = alias_dyn!(Display, AliasPtr::new(5));
+/// assert_eq!(x.to_string(), "5");
+/// # unsafe { x.delete(); }
+/// ```
+#[macro_export]
+macro_rules! alias_dyn {
+    ($trait:path, $ptr:expr) => {{
+        let ptr = $ptr;
+        let coerce = |p| p as *mut dyn $trait;
+        // Safety: an unsizing cast preserves the address.
+        unsafe { $crate::AliasPtr::__map_raw(ptr, coerce) }
+    }};
+}
+
+unsafe impl<T: ?Sized> Send for AliasPtr<T> where T: Send + Sync {}
+unsafe impl<T: ?Sized> Sync for AliasPtr<T> where T: Send + Sync {}
+
+// Like `Box`, moving an `AliasPtr` never moves its target,
+// and `AliasPtr` never hands out `Pin<&mut T>`, so it is `Unpin` even if `T` isn't.
+// This allows holding `AliasPtr`s across `.await` points in `Unpin` futures.
+impl<T: ?Sized> Unpin for AliasPtr<T> {}
+
+// Unlike `Box<T>`, an `AliasPtr<T>` may be aliased, so other copies can observe
+// a `T` left in a broken state by a panic. So follow the bounds of `&T` instead,
+// requiring `T: RefUnwindSafe` (which excludes `Cell` and `RefCell`).
+impl<T: ?Sized + RefUnwindSafe> UnwindSafe for AliasPtr<T> {}
+impl<T: ?Sized + RefUnwindSafe> RefUnwindSafe for AliasPtr<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    #[cfg(not(feature = "checked"))]
+    use std::mem::size_of;
+
+    struct AliasedPair(AliasPtr<Cell<i32>>, AliasPtr<Cell<i32>>);
+
+    impl AliasedPair {
+        fn new(x: i32) -> AliasedPair {
+            let x = AliasPtr::new(Cell::new(x));
+            AliasedPair(x.copy(), x)
+        }
+    }
+
+    impl Drop for AliasedPair {
+        fn drop(&mut self) {
+            unsafe {
+                self.0.copy().delete();
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "checked"))]
+    fn test_option_size_of() {
+        assert_eq!(size_of::<usize>(), size_of::<AliasPtr<i32>>());
+        assert_eq!(size_of::<usize>(), size_of::<Option<AliasPtr<i32>>>());
+    }
+
+    #[test]
+    fn test_aliased_pair() {
+        let pair = AliasedPair::new(1);
+        pair.0.set(42);
+        assert_eq!(pair.1.get(), 42);
+    }
+
+    #[test]
+    fn test_from_array() {
+        let buf: AliasPtr<[i32]> = [1, 2, 3].into();
+        assert_eq!(buf.len(), 3);
+        assert_eq!(&*buf, &[1, 2, 3]);
+        unsafe {
+            buf.delete();
+        }
+    }
+
+    #[test]
+    fn test_from_elem() {
+        let buf = AliasPtr::from_elem(0u8, 64);
+        assert_eq!(buf.len(), 64);
+        assert!(buf.iter().all(|&x| x == 0));
+        unsafe {
+            buf.delete();
+        }
+    }
+
+    #[test]
+    fn test_copy_from_slice() {
+        let src: &[u8] = b"hello";
+        let buf = AliasPtr::copy_from_slice(src);
+        assert_eq!(&*buf, src);
+        assert_ne!(AliasPtr::as_ptr(&buf) as *const u8, src.as_ptr());
+        unsafe {
+            buf.delete();
+        }
+    }
+
+    #[test]
+    fn test_from_ptr() {
+        let p: *const i32 = Box::into_raw(Box::new(5));
+        let x = unsafe { AliasPtr::from_ptr(p) };
+        assert_eq!(*x, 5);
+        assert_eq!(AliasPtr::as_ptr(&x), p);
+        assert_eq!(AliasPtr::as_mut_ptr(&x) as *const i32, p);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "dereference of deleted AliasPtr")]
+    fn test_checked_dangling_deref() {
+        let x = AliasPtr::new(1);
+        let y = x.copy();
+        unsafe {
+            x.delete();
+        }
+        let _ = *y;
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "delete of already-deleted AliasPtr")]
+    fn test_checked_double_delete() {
+        let x = AliasPtr::new(1);
+        let y = x.copy();
+        unsafe {
+            x.delete();
+            y.delete();
+        }
+    }
+
+    #[test]
+    fn test_leaked_static() {
+        use std::sync::OnceLock;
+
+        static CONFIG: OnceLock<AliasPtr<i32>> = OnceLock::new();
+
+        let config = CONFIG.get_or_init(|| AliasPtr::new(42)).copy();
+        assert_eq!(*config, 42);
+        assert_eq!(**CONFIG.get().unwrap(), 42);
+    }
+
+    #[test]
+    #[cfg(feature = "bytemuck")]
+    fn test_bytes_round_trip() {
+        #[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+        #[repr(C)]
+        struct Config {
+            width: u32,
+            height: u32,
+        }
+
+        let config = AliasPtr::new(Config {
+            width: 640,
+            height: 480,
+        });
+        let bytes = AliasPtr::as_bytes(&config);
+        assert_eq!(bytes.len(), 8);
+
+        let copy = AliasPtr::<Config>::from_bytes(bytes);
+        assert_eq!(*copy, *config);
+        unsafe {
+            copy.delete();
+            config.delete();
+        }
+    }
+
+    #[test]
+    fn test_read_write() {
+        let x = AliasPtr::new(String::from("hello"));
+        let y = x.copy();
+        unsafe {
+            let old = AliasPtr::read(&x);
+            AliasPtr::write(&y, String::from("world"));
+            assert_eq!(old, "hello");
+        }
+        assert_eq!(*x, "world");
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_alias_dyn() {
+        trait Shape {
+            fn area(&self) -> i32;
+        }
+
+        struct Square(i32);
+
+        impl Shape for Square {
+            fn area(&self) -> i32 {
+                self.0 * self.0
+            }
+        }
+
+        let square = AliasPtr::new(Square(3));
+        let alias = square.copy();
+        let shape: AliasPtr<dyn Shape> = alias_dyn!(Shape, square);
+        assert_eq!(shape.area(), 9);
+        assert_eq!((*alias).0, 3);
+        unsafe {
+            shape.delete();
+        }
+    }
+
+    #[test]
+    fn test_unpin() {
+        fn assert_unpin<T: Unpin>() {}
+        assert_unpin::<AliasPtr<std::marker::PhantomPinned>>();
+    }
+
+    #[test]
+    fn test_alias_across_await() {
+        use std::future::Future;
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+
+        /// Returns `Pending` once before completing.
+        struct YieldOnce(bool);
+
+        impl Future for YieldOnce {
+            type Output = ();
+            fn poll(mut self: std::pin::Pin<&mut Self>, _cx: &mut Context) -> Poll<()> {
+                if self.0 {
+                    Poll::Ready(())
+                } else {
+                    self.0 = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let pair = AliasedPair::new(0);
+        let alias = pair.1.copy();
+        let mut fut = pin!(async move {
+            alias.set(1);
+            YieldOnce(false).await;
+            alias.set(alias.get() + 1);
+        });
+
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(pair.0.get(), 1);
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+        assert_eq!(pair.0.get(), 2);
+    }
+
+    #[test]
+    fn test_into_array() {
+        let buf: AliasPtr<[i32]> = [1, 2, 3].into();
+        let alias = buf.copy();
+
+        let buf = AliasPtr::into_array::<4>(buf).err().unwrap();
+        assert_eq!(buf.len(), 3);
+
+        let array = AliasPtr::into_array::<3>(buf).ok().unwrap();
+        assert_eq!(*array, [1, 2, 3]);
+        assert_eq!(AliasPtr::as_ptr(&array) as *const i32, alias.as_ptr());
+        unsafe {
+            array.delete();
+        }
+    }
+
+    #[test]
+    fn test_derive_clone() {
+        struct NotClone(i32);
+
+        #[derive(Clone)]
+        struct S {
+            p: AliasPtr<NotClone>,
+        }
+
+        let s = S {
+            p: AliasPtr::new(NotClone(1)),
+        };
+        let t = s.clone();
+        assert_eq!((*t.p).0, 1);
+        unsafe {
+            s.p.delete();
+        }
+    }
+
+    #[test]
+    fn test_element_aliases() {
+        let buf: AliasPtr<[Cell<i32>]> = [Cell::new(0), Cell::new(0), Cell::new(0)].into();
+        let elems = AliasPtr::element_aliases(&buf);
+        assert_eq!(elems.len(), 3);
+        for (i, elem) in elems.iter().enumerate() {
+            elem.set(i as i32 * 10);
+        }
+        assert_eq!(buf.iter().map(Cell::get).collect::<Vec<_>>(), [0, 10, 20]);
+
+        drop(elems);
+        unsafe {
+            buf.delete();
+        }
+    }
+
+    #[test]
+    fn test_alias_dyn_upcast() {
+        trait Super {
+            fn name(&self) -> &'static str;
+        }
+
+        trait Sub: Super {}
+
+        struct Component;
+
+        impl Super for Component {
+            fn name(&self) -> &'static str {
+                "component"
+            }
+        }
+
+        impl Sub for Component {}
+
+        let sub: AliasPtr<dyn Sub> = alias_dyn!(Sub, AliasPtr::new(Component));
+        let sup: AliasPtr<dyn Super> = alias_dyn!(Super, sub);
+        assert_eq!(sup.name(), "component");
+        unsafe {
+            sup.delete();
+        }
+    }
+
+    #[test]
+    fn test_pointer_arithmetic() {
+        let buf: AliasPtr<[Cell<i32>]> = [Cell::new(0), Cell::new(1), Cell::new(2)].into();
+        let first = AliasPtr::element_aliases(&buf).swap_remove(0);
+
+        let last = unsafe { AliasPtr::add(first.copy(), 2) };
+        assert_eq!(last.get(), 2);
+        let middle = unsafe { AliasPtr::sub(last.copy(), 1) };
+        assert_eq!(middle.get(), 1);
+        let first_again = unsafe { AliasPtr::offset(middle.copy(), -1) };
+        assert_eq!(AliasPtr::as_ptr(&first_again), AliasPtr::as_ptr(&first));
+
+        middle.set(10);
+        assert_eq!(buf[1].get(), 10);
+        unsafe {
+            buf.delete();
+        }
+    }
+
+    #[test]
+    fn test_into_box() {
+        let x = AliasPtr::new(String::from("hello"));
+        let b = unsafe { AliasPtr::into_box(x) };
+        assert_eq!(*b, "hello");
+    }
+
+    #[test]
+    fn test_into_pin_box() {
+        use std::future::Future;
+        use std::task::{Context, Poll, Waker};
+
+        struct Countdown(u32);
+
+        impl Future for Countdown {
+            type Output = &'static str;
+            fn poll(mut self: std::pin::Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
+                if self.0 == 0 {
+                    Poll::Ready("done")
+                } else {
+                    self.0 -= 1;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let fut = AliasPtr::new(Countdown(1));
+        let mut fut = unsafe { AliasPtr::into_pin_box(fut) };
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready("done"));
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let x = AliasPtr::new([1u8; 64]);
+        let y = AliasPtr::duplicate(&x);
+        assert_eq!(*x, *y);
+        assert_ne!(AliasPtr::as_ptr(&x), AliasPtr::as_ptr(&y));
+
+        unsafe {
+            AliasPtr::write(&y, [2u8; 64]);
+        }
+        assert_eq!(*x, [1u8; 64]);
+        assert_eq!(*y, [2u8; 64]);
+        unsafe {
+            x.delete();
+            y.delete();
+        }
+    }
+
+    #[test]
+    fn test_type_id() {
+        let x: AliasPtr<dyn Any> = alias_dyn!(Any, AliasPtr::new(5u32));
+        assert_eq!(AliasPtr::type_id(&x), TypeId::of::<u32>());
+        assert_ne!(x.type_id(), TypeId::of::<u32>());
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_try_into_inner() {
+        let x = AliasPtr::new(String::from("hello"));
+        assert_eq!(
+            unsafe { AliasPtr::try_into_inner(x) }.ok().unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn test_checked_try_into_inner_aliased() {
+        let x = AliasPtr::new(1);
+        let y = x.copy();
+        let x = unsafe { AliasPtr::try_into_inner(x) }.err().unwrap();
+        drop(y);
+        assert_eq!(unsafe { AliasPtr::try_into_inner(x) }.ok(), Some(1));
+    }
+
+    #[test]
+    fn test_arc_round_trip() {
+        let x = AliasPtr::new(String::from("hello"));
+        let arc = unsafe { AliasPtr::into_arc(x) };
+        let arc2 = arc.clone();
+        let arc = AliasPtr::from_arc(arc).err().unwrap();
+        drop(arc2);
+
+        let x = AliasPtr::from_arc(arc).ok().unwrap();
+        assert_eq!(*x, "hello");
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_rc_round_trip() {
+        let x: AliasPtr<[i32]> = [1, 2].into();
+        let rc: Rc<[i32]> = unsafe { AliasPtr::into_rc(x) };
+        assert_eq!(*rc, [1, 2]);
+
+        let x = AliasPtr::from_rc(Rc::new(3)).ok().unwrap();
+        assert_eq!(*x, 3);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_method_chaining() {
+        let mut seen = 0;
+        let mut aliases = Vec::new();
+        let x = AliasPtr::new(Cell::new(7))
+            .inspect(|x| seen = x.get())
+            .alias_to(&mut aliases)
+            .alias_to(&mut aliases);
+        assert_eq!(seen, 7);
+        assert_eq!(aliases.len(), 2);
+
+        aliases[0].set(8);
+        assert_eq!(aliases[1].get(), 8);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_transmute() {
+        #[repr(transparent)]
+        struct Meters(u32);
+
+        let x = AliasPtr::new(5u32);
+        let addr = AliasPtr::as_ptr(&x) as *const ();
+        let m: AliasPtr<Meters> = unsafe { AliasPtr::transmute(x) };
+        assert_eq!((*m).0, 5);
+        assert_eq!(AliasPtr::as_ptr(&m) as *const (), addr);
+        unsafe {
+            m.delete();
+        }
+    }
+
+    #[test]
+    fn test_box_slice_round_trip() {
+        let mut v = Vec::with_capacity(10);
+        v.extend([1, 2, 3].iter().copied());
+        let b: Box<[i32]> = v.into_boxed_slice();
+        let addr = b.as_ptr();
+
+        let x = AliasPtr::from(b);
+        assert_eq!(AliasPtr::as_ptr(&x) as *const i32, addr);
+
+        let b: Box<[i32]> = unsafe { AliasPtr::into_box(x) };
+        assert_eq!(b.as_ptr(), addr);
+        assert_eq!(*b, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_catch_unwind() {
+        let x = AliasPtr::new(1);
+        let y = x.copy();
+        let result = std::panic::catch_unwind(move || {
+            assert_eq!(*y, 1);
+            panic!("oops");
+        });
+        assert!(result.is_err());
+        assert_eq!(*x, 1);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_with_mut() {
+        let mut x = AliasPtr::new(vec![1, 2]);
+        let y = x.copy();
+        let len = unsafe {
+            AliasPtr::with_mut(&mut x, |v| {
+                v.push(3);
+                v.len()
+            })
+        };
+        assert_eq!(len, 3);
+        assert_eq!(*y, [1, 2, 3]);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_const_accessors() {
+        // Only compiles if the accessors are `const fn`.
+        const fn pointers(p: &AliasPtr<()>) -> (*const (), *mut (), NonNull<()>) {
+            (
+                AliasPtr::as_ptr(p),
+                AliasPtr::as_mut_ptr(p),
+                AliasPtr::as_non_null(p),
+            )
+        }
+
+        let x = AliasPtr::new(());
+        let (a, b, c) = pointers(&x);
+        assert_eq!(a, b as *const ());
+        assert_eq!(b, c.as_ptr());
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_try_from_raw() {
+        assert!(unsafe { AliasPtr::<i32>::try_from_raw(std::ptr::null_mut()) }.is_none());
+
+        let p = Box::into_raw(Box::new(1));
+        let x = unsafe { AliasPtr::try_from_raw(p) }.unwrap();
+        assert_eq!(*x, 1);
+        let y = unsafe { AliasPtr::from_non_null(AliasPtr::as_non_null(&x)) };
+        assert_eq!(AliasPtr::as_mut_ptr(&y), p);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_c_void_round_trip() {
+        extern "C" fn on_event(userdata: *const c_void) {
+            let counter = unsafe { AliasPtr::<Cell<i32>>::from_c_void(userdata) };
+            counter.set(counter.get() + 1);
+        }
+
+        fn register(callback: extern "C" fn(*const c_void), userdata: *const c_void) {
+            callback(userdata);
+            callback(userdata);
+        }
+
+        let counter = AliasPtr::new(Cell::new(0));
+        register(on_event, AliasPtr::as_c_void(&counter));
+        assert_eq!(counter.get(), 2);
+        unsafe {
+            counter.delete();
+        }
+    }
+
+    #[test]
+    fn test_reuse_uninit_slot() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let slot = AliasPtr::<MaybeUninit<Rc<()>>>::new_uninit();
+        let alias = slot.copy();
+        for _ in 0..2 {
+            unsafe {
+                AliasPtr::write(&slot, MaybeUninit::new(counter.clone()));
+                assert!(Rc::ptr_eq(alias.assume_init_ref(), &counter));
+                assert_eq!(Rc::strong_count(&counter), 2);
+                AliasPtr::drop_in_place(&slot);
+            }
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        unsafe {
+            AliasPtr::write(&slot, MaybeUninit::new(counter.clone()));
+            let slot = AliasPtr::assume_init(slot);
+            assert_eq!(Rc::strong_count(&*slot), 2);
+            slot.delete();
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let a = AliasPtr::new(1);
+        let b = AliasPtr::new(2);
+        let mut x = a.clone();
+        for p in [&b, &a, &b] {
+            x.clone_from(p);
+            assert_eq!(AliasPtr::as_ptr(&x), AliasPtr::as_ptr(p));
+        }
+        assert_eq!(*x, 2);
+        unsafe {
+            a.delete();
+            b.delete();
+        }
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut x: AliasPtr<[i32]> = [5, 3, 4, 1, 2].into();
+        let y = x.copy();
+        unsafe {
+            AliasPtr::sort(&mut x);
+        }
+        assert_eq!(*y, [1, 2, 3, 4, 5]);
+
+        unsafe {
+            AliasPtr::with_mut(&mut x, |s| s.reverse());
+            AliasPtr::sort_unstable(&mut x);
+        }
+        assert_eq!(*y, [1, 2, 3, 4, 5]);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_str_into_bytes() {
+        let s = AliasPtr::from(String::from("héllo").into_boxed_str());
+        let alias = s.copy();
+        let bytes: AliasPtr<[u8]> = s.into();
+        assert_eq!(*bytes, *"héllo".as_bytes());
+        assert_eq!(AliasPtr::as_ptr(&bytes) as *const u8, alias.as_ptr());
+        unsafe {
+            bytes.delete();
+        }
+    }
+
+    #[test]
+    fn test_deref_stable_across_moves() {
+        let a = AliasPtr::new([0u8; 16]);
+        let addr = &*a as *const [u8; 16];
+        let b = a;
+        assert_eq!(&*b as *const _, addr);
+        let c = vec![b];
+        assert_eq!(&*c[0] as *const _, addr);
+        for x in c {
+            unsafe {
+                x.delete();
+            }
+        }
+    }
+
+    #[test]
+    fn test_uninit_views() {
+        struct Pair {
+            name: String,
+            value: i32,
+        }
+
+        let mut x = AliasPtr::new(Pair {
+            name: String::from("a"),
+            value: 1,
+        });
+        let y = x.copy();
+        unsafe {
+            let uninit = AliasPtr::as_uninit_mut(&mut x).as_mut_ptr();
+            drop(std::ptr::addr_of_mut!((*uninit).name).replace(String::from("b")));
+            std::ptr::addr_of_mut!((*uninit).value).write(2);
+        }
+        assert_eq!(y.name, "b");
+        assert_eq!(y.value, 2);
+        assert_eq!(AliasPtr::as_uninit(&y).as_ptr(), AliasPtr::as_ptr(&x));
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_resize() {
+        let x = AliasPtr::copy_from_slice(b"abc");
+        let x = unsafe { AliasPtr::resize(x, 5) };
+        assert_eq!(*x, *b"abc\0\0");
+        let x = unsafe { AliasPtr::resize(x, 2) };
+        assert_eq!(*x, *b"ab");
+        let x = unsafe { AliasPtr::resize(x, 0) };
+        assert!(x.is_empty());
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_slice_into_iter() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let x: AliasPtr<[Rc<()>]> = [counter.clone(), counter.clone()].into();
+        let mut iter = unsafe { AliasPtr::into_iter(x) };
+        let first = iter.next().unwrap();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(first);
+
+        let x: AliasPtr<[String]> = [String::from("a"), String::from("b")].into();
+        let strings: Vec<String> = unsafe { AliasPtr::into_iter(x) }.collect();
+        assert_eq!(strings, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_from_static() {
+        static DATA: [u8; 4] = *b"\x7fELF";
+        let stat: AliasPtr<[u8]> = AliasPtr::from_static(&DATA);
+        let heap = AliasPtr::copy_from_slice(&DATA[..]);
+        for p in [&stat, &heap] {
+            assert_eq!(p[1..], *b"ELF");
+        }
+        assert_eq!(AliasPtr::as_ptr(&stat), &DATA[..] as *const [u8]);
+        unsafe {
+            heap.delete();
+        }
+    }
+
+    #[test]
+    fn test_optional_pointers() {
+        let a = AliasPtr::new(1);
+        let mut slot: Option<AliasPtr<i32>> = None;
+        assert!(slot.is_none());
+
+        assert!(slot.replace(a.copy()).is_none());
+        assert!(slot.as_ref().is_some_and(|p| **p == 1));
+        assert_eq!(
+            slot.as_ref().map(|p| AliasPtr::map_pointee(p, |x| x + 1)),
+            Some(2)
+        );
+
+        let taken = slot.take().unwrap();
+        assert!(slot.is_none());
+        assert_eq!(AliasPtr::as_ptr(&taken), AliasPtr::as_ptr(&a));
+        unsafe {
+            a.delete();
+        }
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut x: AliasPtr<[i32]> = [1, 2, 3].into();
+        let y = x.copy();
+        unsafe {
+            let slice = AliasPtr::as_mut_slice(&mut x);
+            slice[0] = 10;
+            slice.swap(1, 2);
+        }
+        assert_eq!(AliasPtr::as_slice(&y), [10, 3, 2]);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_try_deref() {
+        let a = AliasPtr::new(1);
+        let b = a.copy();
+        assert_eq!(AliasPtr::try_deref(&b), Some(&1));
+        unsafe {
+            a.delete();
+        }
+        #[cfg(feature = "checked")]
+        assert_eq!(AliasPtr::try_deref(&b), None);
+    }
+
+    #[test]
+    fn test_write_slice() {
+        let src: Vec<u8> = (0..100).collect();
+        let buf = AliasPtr::<[u8]>::new_uninit_slice(src.len());
+        let buf = AliasPtr::write_slice(buf, &src);
+        assert_eq!(*buf, *src);
+        unsafe {
+            buf.delete();
+        }
+    }
+
+    #[test]
+    fn test_write_slice_mismatch() {
+        let buf = AliasPtr::<[u8]>::new_uninit_slice(2);
+        let alias = buf.copy();
+        let result = std::panic::catch_unwind(|| AliasPtr::write_slice(buf, b"abc"));
+        assert!(result.is_err());
+        unsafe {
+            alias.delete();
+        }
+    }
+
+    #[test]
+    fn test_clone_value_from() {
+        let dst = AliasPtr::new(String::with_capacity(16));
+        let alias = dst.copy();
+        let addr = AliasPtr::as_ptr(&dst);
+        let buf = (*dst).as_ptr();
+
+        unsafe {
+            AliasPtr::clone_value_from(&dst, &String::from("hello"));
+        }
+        assert_eq!(AliasPtr::as_ptr(&dst), addr);
+        assert_eq!(*alias, "hello");
+        // String::clone_from reuses the existing buffer.
+        assert_eq!((*alias).as_ptr(), buf);
+        unsafe {
+            dst.delete();
+        }
+    }
+
+    #[test]
+    fn test_map_elements() {
+        let x: AliasPtr<[i32]> = [1, -2, i32::MAX].into();
+        let y: AliasPtr<[i64]> = unsafe { AliasPtr::map_elements(x, |v| v as i64 * 2) };
+        assert_eq!(*y, [2, -4, i32::MAX as i64 * 2]);
+
+        let z: AliasPtr<[String]> = unsafe { AliasPtr::map_elements(y, |v| v.to_string()) };
+        assert_eq!(*z, ["2", "-4", "4294967294"]);
+        unsafe {
+            z.delete();
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        struct Field(i32);
+
+        impl Field {
+            fn deref(&self) -> i32 {
+                self.0
+            }
+
+            fn copy(&self) -> Field {
+                Field(self.0 + 1)
+            }
+        }
+
+        let x = AliasPtr::new(Field(1));
+        let field = AliasPtr::get(&x);
+        assert_eq!(field.deref(), 1);
+        assert_eq!(field.copy().0, 2);
+        // Method syntax on the pointer finds `AliasPtr::copy()` instead.
+        let alias: AliasPtr<Field> = x.copy();
+        assert_eq!(AliasPtr::get(&alias).deref(), 1);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_as_thin_ptr() {
+        let x: AliasPtr<[u16]> = [1, 2, 3].into();
+        let void = &x[0] as *const u16 as *const ();
+        assert!(std::ptr::eq(AliasPtr::as_thin_ptr(&x), void));
+
+        let y = AliasPtr::new(5);
+        let dyn_y = alias_dyn!(std::fmt::Display, y.copy());
+        assert_eq!(
+            AliasPtr::as_thin_ptr(&dyn_y),
+            AliasPtr::as_c_void(&y) as *const ()
+        );
+        unsafe {
+            x.delete();
+            y.delete();
+        }
+    }
+
+    #[test]
+    fn test_assume_unique() {
+        let x = AliasPtr::new(1);
+        {
+            let _y = x.copy();
+        }
+        unsafe {
+            AliasPtr::assume_unique(&x);
+            x.delete();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "AliasPtr assumed unique, but other copies exist")]
+    fn test_assume_unique_aliased() {
+        let x = AliasPtr::new(1);
+        let _y = x.copy();
+        unsafe {
+            AliasPtr::assume_unique(&x);
+        }
+    }
+
+    #[test]
+    fn test_lend() {
+        fn consume<'a>(iter: impl Iterator<Item = &'a String>) -> usize {
+            iter.map(String::len).sum()
+        }
+
+        let x: AliasPtr<[String]> = [String::from("ab"), String::from("c")].into();
+        assert_eq!(consume(AliasPtr::lend(&x)), 3);
+        assert_eq!(consume(AliasPtr::lend(&x).rev().take(1)), 1);
+        assert_eq!(*x, ["ab", "c"]);
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let x = AliasPtr::new(-42);
+        assert_eq!(format!("{}", x), "-42");
+        assert_eq!(format!("{:+05}", x.copy()), "-0042");
+        assert_eq!(x.to_string(), "-42");
+        unsafe {
+            x.delete();
+        }
+    }
+
+    #[test]
+    fn test_error() {
+        #[derive(Debug)]
+        struct Outer(std::num::ParseIntError);
+
+        impl fmt::Display for Outer {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("invalid count")
+            }
+        }
+
+        impl Error for Outer {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        fn parse(s: &str) -> Result<u32, AliasPtr<dyn Error>> {
+            s.parse().map_err(|e| {
+                let err: Box<dyn Error> = Box::new(Outer(e));
+                AliasPtr::from(err)
+            })
+        }
+
+        assert_eq!(parse("3").unwrap(), 3);
+        let err = parse("x").unwrap_err();
+        assert_eq!(err.to_string(), "invalid count");
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), "invalid digit found in string");
+        assert!(source.is::<std::num::ParseIntError>());
+        unsafe {
+            err.delete();
+        }
+    }
+
+    #[test]
+    fn test_new_with() {
+        // Larger than a test thread's stack, so building it there would overflow.
+        const LEN: usize = 4 << 20;
+        let x = unsafe {
+            AliasPtr::<[u8; LEN]>::new_with(|slot| {
+                let p = slot.as_mut_ptr() as *mut u8;
+                p.write_bytes(7, LEN);
+                p.add(LEN - 1).write(9);
+            })
+        };
+        assert_eq!(x[..3], [7, 7, 7]);
+        assert_eq!(x[LEN - 1], 9);
+        unsafe {
+            x.delete();
+        }
+
+        let result = std::panic::catch_unwind(|| unsafe {
+            AliasPtr::<String>::new_with(|_| panic!("failed to initialize"))
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_into_raw_with_layout() {
+        use std::alloc::dealloc;
+
+        unsafe fn free<T: ?Sized>((p, layout): (*mut T, Layout)) {
+            std::ptr::drop_in_place(p);
+            if layout.size() != 0 {
+                dealloc(p as *mut u8, layout);
+            }
+        }
+
+        let x = AliasPtr::new(String::from("abc"));
+        let (p, layout) = AliasPtr::into_raw_with_layout(x);
+        assert_eq!(layout, Layout::new::<String>());
+        assert_eq!(unsafe { &*p }, "abc");
+        unsafe { free((p, layout)) };
+
+        let x: AliasPtr<[u32]> = [1, 2, 3].into();
+        let raw = AliasPtr::into_raw_with_layout(x);
+        assert_eq!(raw.1, Layout::array::<u32>(3).unwrap());
+        unsafe { free(raw) };
+
+        let raw = AliasPtr::into_raw_with_layout(AliasPtr::new(()));
+        assert_eq!(raw.1.size(), 0);
+        unsafe { free(raw) };
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn test_into_raw_with_layout_kills_aliases() {
+        let x = AliasPtr::new(5u32);
+        let alias = x.copy();
+        let (p, layout) = AliasPtr::into_raw_with_layout(x);
+        let result = std::panic::catch_unwind(|| *alias);
+        assert!(result.is_err());
+        unsafe { std::alloc::dealloc(p as *mut u8, layout) };
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        let values: Vec<_> = (0..16u64).map(AliasPtr::new).collect();
+        let aliases: Vec<_> = values.iter().map(AliasPtr::copy).collect();
+        let sum: u64 = aliases
+            .iter()
+            .map(|p| unsafe { *AliasPtr::get_unchecked(p) })
+            .sum();
+        assert_eq!(sum, aliases.iter().map(|p| **p).sum::<u64>());
+        assert_eq!(sum, 120);
+        for p in values {
+            unsafe {
+                p.delete();
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_fn() {
+        use std::rc::Rc;
+
+        let table = AliasPtr::from_fn(4, |i| format!("#{}", i));
+        assert_eq!(*table, ["#0", "#1", "#2", "#3"]);
+        let empty = AliasPtr::from_fn(0, |_| -> String { unreachable!() });
+        assert!(empty.is_empty());
+        unsafe {
+            table.delete();
+            empty.delete();
+        }
+
+        let counter = Rc::new(());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            AliasPtr::from_fn(5, |i| {
+                if i == 3 {
+                    panic!("failed to initialize");
+                }
+                counter.clone()
+            })
+        }));
+        assert!(result.is_err());
+        // The three initialized elements were dropped.
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_ptr_eq() {
+        // Only compiles if `as_thin_ptr()` is a `const fn`.
+        const fn thin<T: ?Sized>(p: &AliasPtr<T>) -> *const () {
+            AliasPtr::as_thin_ptr(p)
+        }
+        const { assert!(AliasPtr::<()>::is_zst()) };
+
+        let x: AliasPtr<[u8]> = [1, 2].into();
+        let y = x.copy();
+        let z: AliasPtr<[u8]> = [1, 2].into();
+        assert_eq!(thin(&x), AliasPtr::as_ptr(&x) as *const ());
+        assert!(AliasPtr::ptr_eq(&x, &y));
+        assert!(!AliasPtr::ptr_eq(&x, &z));
+
+        // Slice lengths are ignored.
+        let first = std::ptr::slice_from_raw_parts_mut(AliasPtr::as_mut_ptr(&x) as *mut u8, 1);
+        let first = unsafe { AliasPtr::from_raw(first) };
+        assert!(AliasPtr::ptr_eq(&x, &first));
+        unsafe {
+            x.delete();
+            z.delete();
+        }
+    }
+
+    #[test]
+    fn test_try_alias() {
+        use std::cell::{Cell, RefCell};
+        use std::rc::Rc;
+
+        /// Tries to alias itself while being dropped.
+        struct Reentrant {
+            this: RefCell<Option<AliasPtr<Reentrant>>>,
+            result: Rc<Cell<Option<bool>>>,
+        }
+
+        impl Drop for Reentrant {
+            fn drop(&mut self) {
+                let this = self.this.take().unwrap();
+                self.result.set(Some(AliasPtr::try_alias(&this).is_ok()));
+            }
+        }
+
+        let result = Rc::new(Cell::new(None));
+        let x = AliasPtr::new(Reentrant {
+            this: RefCell::new(None),
+            result: result.clone(),
+        });
+        let alias = AliasPtr::try_alias(&x).unwrap();
+        *x.this.borrow_mut() = Some(alias);
+        unsafe {
+            x.delete();
+        }
+        // Deletion is only detected with the `checked` feature.
+        assert_eq!(result.get(), Some(!cfg!(feature = "checked")));
+    }
+
+    #[test]
+    fn test_get_element() {
+        use std::cell::Cell;
+
+        let nodes: AliasPtr<[Cell<i32>]> = AliasPtr::from_fn(3, |i| Cell::new(i as i32));
+        let first = AliasPtr::get_element(&nodes, 0);
+        let last = AliasPtr::get_element(&nodes, 2);
+        first.set(10);
+        last.set(last.get() + 10);
+        assert_eq!(AliasPtr::as_ptr(&first), &nodes[0] as *const _);
+        let values: Vec<i32> = nodes.iter().map(Cell::get).collect();
+        assert_eq!(values, [10, 1, 12]);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            AliasPtr::get_element(&nodes, 3)
+        }));
+        assert!(result.is_err());
+        unsafe {
+            nodes.delete();
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        let x: AliasPtr<[u64]> = AliasPtr::from((1..=10).collect::<Vec<u64>>());
+        let sums: Vec<u64> = std::thread::scope(|s| {
+            let threads: Vec<_> = AliasPtr::chunks(&x, 3)
+                .map(|chunk| s.spawn(move || chunk.iter().sum::<u64>()))
+                .collect();
+            threads.into_iter().map(|t| t.join().unwrap()).collect()
+        });
+        assert_eq!(sums, [6, 15, 24, 10]);
+
+        // Chunk and window bounds match those of the slice methods.
+        for size in 1..=12 {
+            let chunks: Vec<Vec<u64>> = AliasPtr::chunks(&x, size).map(|c| c.to_vec()).collect();
+            let windows: Vec<Vec<u64>> = AliasPtr::windows(&x, size).map(|w| w.to_vec()).collect();
+            assert_eq!(chunks, x.chunks(size).collect::<Vec<_>>());
+            assert_eq!(windows, x.windows(size).collect::<Vec<_>>());
+        }
+        let empty: AliasPtr<[u64]> = AliasPtr::from(Vec::new());
+        assert_eq!(AliasPtr::chunks(&empty, 2).count(), 0);
+        assert_eq!(AliasPtr::windows(&empty, 2).count(), 0);
+
+        let result = std::panic::catch_unwind(|| AliasPtr::chunks(&x, 0).count());
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(|| AliasPtr::windows(&x, 0).count());
+        assert!(result.is_err());
+        unsafe {
+            x.delete();
+            empty.delete();
+        }
+    }
+
+    #[test]
+    fn test_windows_overlap() {
+        use std::cell::Cell;
+
+        let x: AliasPtr<[Cell<u64>]> = AliasPtr::from((0..5).map(Cell::new).collect::<Vec<_>>());
+        let windows: Vec<AliasPtr<[Cell<u64>]>> = AliasPtr::windows(&x, 2).collect();
+
+        // Adjacent windows alias the element they share.
+        for (i, pair) in windows.windows(2).enumerate() {
+            assert!(std::ptr::eq(&pair[0][1], &pair[1][0]));
+            assert!(std::ptr::eq(&pair[0][1], &x[i + 1]));
+        }
+
+        // Writes through one window are visible through the next.
+        for window in &windows {
+            window[1].set(window[0].get() + window[1].get());
+        }
+        let sums: Vec<u64> = x.iter().map(Cell::get).collect();
+        assert_eq!(sums, [0, 1, 3, 6, 10]);
+        unsafe {
+            x.delete();
+        }
     }
 
     // /// Does not compile, as expected.
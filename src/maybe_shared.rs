@@ -0,0 +1,140 @@
+use crate::{Alias, AliasPtr};
+use std::fmt;
+use std::ops::Deref;
+
+/// A pointer which either owns its target (and deletes it when dropped),
+/// or is an alias of a target owned elsewhere.
+///
+/// This lets code which sometimes owns a value exclusively,
+/// and sometimes shares one owned by another data structure,
+/// handle both cases through a single type.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::{AliasPtr, MaybeShared};
+/// let shared_value = AliasPtr::new(String::from("shared"));
+/// let nodes = vec![
+///     MaybeShared::owned(String::from("owned")),
+///     MaybeShared::shared(shared_value.copy()),
+/// ];
+/// assert_eq!(*nodes[0], "owned");
+/// assert_eq!(*nodes[1], "shared");
+///
+/// // Only deletes the owned string.
+/// drop(nodes);
+/// # unsafe { shared_value.delete(); }
+/// ```
+pub struct MaybeShared<T: ?Sized> {
+    ptr: AliasPtr<T>,
+    owned: bool,
+}
+
+impl<T> MaybeShared<T> {
+    /// Allocates memory on the heap and then places `x` into it,
+    /// to be deleted when the `MaybeShared` is dropped.
+    pub fn owned(x: T) -> MaybeShared<T> {
+        MaybeShared {
+            ptr: AliasPtr::new(x),
+            owned: true,
+        }
+    }
+}
+
+impl<T: ?Sized> MaybeShared<T> {
+    /// Wraps an alias to a target owned elsewhere.
+    /// Dropping the `MaybeShared` does not delete the target.
+    pub fn shared(ptr: AliasPtr<T>) -> MaybeShared<T> {
+        MaybeShared { ptr, owned: false }
+    }
+
+    /// Takes ownership of `ptr`'s target, deleting it when the `MaybeShared` is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`AliasPtr::delete`] apply
+    /// at the point the `MaybeShared` is dropped.
+    pub unsafe fn from_owner(ptr: AliasPtr<T>) -> MaybeShared<T> {
+        MaybeShared { ptr, owned: true }
+    }
+
+    /// Returns `true` if the target will be deleted when this is dropped.
+    pub fn is_owned(this: &Self) -> bool {
+        this.owned
+    }
+
+    /// Creates an alias to the target, which borrows the `MaybeShared`.
+    ///
+    /// If this `MaybeShared` is owned, an alias converted by [`Alias::into_ptr`]
+    /// must not be dereferenced after it is dropped, and must not be deleted.
+    pub fn alias(this: &Self) -> Alias<'_, T> {
+        // Safety: owned targets are only deleted when this is dropped.
+        // Shared targets are as valid as the alias passed to `shared()`,
+        // which `Deref` already relies on.
+        unsafe { Alias::new(this.ptr.copy()) }
+    }
+}
+
+impl<T: ?Sized> Deref for MaybeShared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.ptr
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MaybeShared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Drop for MaybeShared<T> {
+    fn drop(&mut self) {
+        if self.owned {
+            // Safety: owned targets were either allocated by `owned()`,
+            // or the caller of `from_owner()` upholds the requirements of delete().
+            unsafe {
+                self.ptr.copy().delete();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_only_owned_deletes() {
+        let counter = Rc::new(());
+        let shared_value = AliasPtr::new(counter.clone());
+        {
+            let owned = MaybeShared::owned(counter.clone());
+            let shared = MaybeShared::shared(shared_value.copy());
+            assert!(MaybeShared::is_owned(&owned));
+            assert!(!MaybeShared::is_owned(&shared));
+            assert!(Rc::ptr_eq(&owned, &shared));
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+        assert_eq!(Rc::strong_count(&counter), 2);
+        unsafe {
+            shared_value.delete();
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_from_owner() {
+        let counter = Rc::new(());
+        let value = AliasPtr::new(counter.clone());
+        let owner = unsafe { MaybeShared::from_owner(value.copy()) };
+        assert_eq!(
+            Alias::as_ptr(&MaybeShared::alias(&owner)),
+            AliasPtr::as_ptr(&value)
+        );
+        drop(owner);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::AliasPtr;
+
+/// An iterator which owns the elements it iterates over,
+/// and passes each one by reference to a closure.
+///
+/// Storing a borrowing iterator next to the data it borrows from
+/// is impossible with references, since a struct cannot borrow from itself.
+/// `SelfRefIter` instead holds the elements in an `AliasPtr<[T]>`,
+/// alongside aliases to each element that have not been visited yet.
+/// The aliases are never dereferenced after the elements are deleted on drop,
+/// and the closure cannot smuggle out a reference, since `R` cannot depend
+/// on the lifetime of the borrow.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::SelfRefIter;
+/// let words = vec![String::from("a"), String::from("bcd")];
+/// let lens: Vec<usize> = SelfRefIter::new(words, |s: &String| s.len()).collect();
+/// assert_eq!(lens, [1, 3]);
+/// ```
+pub struct SelfRefIter<T, F> {
+    data: AliasPtr<[T]>,
+    remaining: std::vec::IntoIter<AliasPtr<T>>,
+    f: F,
+}
+
+impl<T, R, F: FnMut(&T) -> R> SelfRefIter<T, F> {
+    /// Moves `data` onto the heap, and iterates over `f` applied to each element.
+    pub fn new(data: Vec<T>, f: F) -> SelfRefIter<T, F> {
+        let data = AliasPtr::from(data.into_boxed_slice());
+        let remaining = AliasPtr::element_aliases(&data).into_iter();
+        SelfRefIter { data, remaining, f }
+    }
+}
+
+impl<T, R, F: FnMut(&T) -> R> Iterator for SelfRefIter<T, F> {
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        let elem = self.remaining.next()?;
+        Some((self.f)(&elem))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl<T, R, F: FnMut(&T) -> R> ExactSizeIterator for SelfRefIter<T, F> {}
+
+impl<T, F> Drop for SelfRefIter<T, F> {
+    fn drop(&mut self) {
+        // Safety: `data` was created from a Box. The remaining element aliases
+        // are dropped along with `self` without being dereferenced.
+        unsafe {
+            self.data.copy().delete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_iterate() {
+        let iter = SelfRefIter::new(vec![3, 1, 2], |x: &i32| x * 10);
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), [30, 10, 20]);
+    }
+
+    #[test]
+    fn test_drop_partway() {
+        let counter = Rc::new(());
+        let data = vec![counter.clone(), counter.clone(), counter.clone()];
+        let mut iter = SelfRefIter::new(data, |rc: &Rc<()>| Rc::strong_count(rc));
+        assert_eq!(iter.next(), Some(4));
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
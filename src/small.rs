@@ -0,0 +1,174 @@
+use crate::{Alias, AliasPtr};
+use std::cell::UnsafeCell;
+use std::marker::PhantomPinned;
+use std::mem::{align_of, size_of, MaybeUninit};
+use std::pin::Pin;
+
+/// A `T` stored inline if it fits in `N` bytes, or on the heap otherwise,
+/// which aliases can point into.
+///
+/// Aliases to inline storage point into the `SmallAliasBox` itself,
+/// so they are only valid while it does not move. Therefore aliases
+/// can only be created through a pinned reference, which guarantees that
+/// the `SmallAliasBox` stays in place until it is dropped.
+/// Pinning alone does not keep aliases from outliving the `SmallAliasBox`,
+/// so they also borrow the pinned reference. As with other owning types,
+/// aliases converted by [`Alias::into_ptr`] must not be dereferenced after
+/// the `SmallAliasBox` is dropped, and must not be deleted.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::SmallAliasBox;
+/// use std::pin::pin;
+///
+/// let small = pin!(SmallAliasBox::<u32, 8>::new(5));
+/// assert!(small.is_inline());
+/// let alias = small.as_ref().alias();
+/// assert_eq!(*alias, 5);
+/// ```
+pub struct SmallAliasBox<T, const N: usize> {
+    storage: Storage<T, N>,
+    _pinned: PhantomPinned,
+}
+
+enum Storage<T, const N: usize> {
+    Inline(Inline<T, N>),
+    Heap(AliasPtr<T>),
+}
+
+/// `N` bytes aligned for a `T`.
+///
+/// The bytes are wrapped in `UnsafeCell`, so references to a `T` with
+/// interior mutability (like `Cell`) may mutate it through the storage.
+#[repr(C)]
+struct Inline<T, const N: usize> {
+    _align: [T; 0],
+    bytes: UnsafeCell<[MaybeUninit<u8>; N]>,
+}
+
+// Safety: shared access to the storage only hands out `&T`,
+// so sharing it is as thread-safe as sharing the heap variant's `AliasPtr`.
+unsafe impl<T: Send + Sync, const N: usize> Sync for SmallAliasBox<T, N> {}
+
+impl<T, const N: usize> SmallAliasBox<T, N> {
+    /// Stores `x` inline if `T` fits in `N` bytes, and on the heap otherwise.
+    pub fn new(x: T) -> SmallAliasBox<T, N> {
+        let storage = if size_of::<T>() <= N {
+            let mut inline = Inline {
+                _align: [],
+                bytes: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            };
+            let p = inline.bytes.get_mut().as_mut_ptr() as *mut T;
+            // Safety: the storage is large enough for a `T`,
+            // and aligned for one by the `[T; 0]` field.
+            debug_assert_eq!(p as usize % align_of::<T>(), 0);
+            unsafe { p.write(x) };
+            Storage::Inline(inline)
+        } else {
+            Storage::Heap(AliasPtr::new(x))
+        };
+        SmallAliasBox {
+            storage,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Returns `true` if the value is stored inline.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline(_))
+    }
+
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &T {
+        match &self.storage {
+            // Safety: the inline storage holds an initialized `T`.
+            Storage::Inline(inline) => unsafe { &*(inline.bytes.get() as *const T) },
+            Storage::Heap(ptr) => ptr,
+        }
+    }
+
+    /// Creates an alias to the value, which borrows the pinned `SmallAliasBox`.
+    pub fn alias(self: Pin<&Self>) -> Alias<'_, T> {
+        let ptr = match &self.get_ref().storage {
+            // Safety: the value is valid until the pinned `SmallAliasBox` is dropped,
+            // and never moves before then.
+            Storage::Inline(inline) => unsafe {
+                AliasPtr::from_ptr(inline.bytes.get() as *const T)
+            },
+            Storage::Heap(ptr) => ptr.copy(),
+        };
+        // Safety: the value is only dropped along with the `SmallAliasBox`,
+        // and is never mutably borrowed.
+        unsafe { Alias::new(ptr) }
+    }
+}
+
+impl<T, const N: usize> Drop for SmallAliasBox<T, N> {
+    fn drop(&mut self) {
+        match &mut self.storage {
+            // Safety: the inline storage holds an initialized `T`.
+            Storage::Inline(inline) => unsafe {
+                (inline.bytes.get_mut().as_mut_ptr() as *mut T).drop_in_place();
+            },
+            // Safety: the heap storage was created by AliasPtr::new().
+            Storage::Heap(ptr) => unsafe { ptr.copy().delete() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_inline() {
+        let counter = Rc::new(());
+        {
+            let small = pin!(SmallAliasBox::<_, 16>::new(counter.clone()));
+            assert!(small.is_inline());
+            let aliases: Vec<_> = (0..3).map(|_| small.as_ref().alias()).collect();
+            let moved = aliases;
+            for alias in &moved {
+                assert!(Rc::ptr_eq(alias, &counter));
+            }
+            assert_eq!(Rc::strong_count(&counter), 2);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_heap() {
+        let counter = Rc::new(());
+        {
+            let small = pin!(SmallAliasBox::<_, 4>::new((counter.clone(), [0u8; 32])));
+            assert!(!small.is_inline());
+            let alias = small.as_ref().alias();
+            assert!(Rc::ptr_eq(&alias.0, &counter));
+            assert_eq!(small.get().1, [0; 32]);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_inline_alignment() {
+        let small = pin!(SmallAliasBox::<u64, 8>::new(u64::MAX));
+        let alias = small.as_ref().alias();
+        assert_eq!(Alias::as_ptr(&alias) as usize % align_of::<u64>(), 0);
+        assert_eq!(*alias, u64::MAX);
+    }
+
+    #[test]
+    fn test_inline_cell() {
+        use std::cell::Cell;
+
+        let small = pin!(SmallAliasBox::<Cell<i32>, 8>::new(Cell::new(1)));
+        assert!(small.is_inline());
+        small.get().set(5);
+        let alias = small.as_ref().alias();
+        alias.set(alias.get() + 1);
+        assert_eq!(small.get().get(), 6);
+    }
+}
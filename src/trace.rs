@@ -0,0 +1,39 @@
+//! Emits `tracing` events when `AliasPtr`s are created, copied, and deleted.
+//!
+//! With the `tracing` feature enabled, each event is logged at the `TRACE` level
+//! with the `alias_ptr` target, and records the target address and the caller's
+//! source location, so the lifecycle of an allocation can be reconstructed from logs.
+//!
+//! Without the feature, these functions are no-ops.
+
+use std::ptr::NonNull;
+
+#[cfg(feature = "tracing")]
+#[track_caller]
+fn event<T: ?Sized>(action: &'static str, p: NonNull<T>) {
+    let addr = p.as_ptr() as *const ();
+    let location = std::panic::Location::caller();
+    tracing::trace!(target: "alias_ptr", ?addr, %location, "{}", action);
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+fn event<T: ?Sized>(_action: &'static str, _p: NonNull<T>) {}
+
+#[cfg_attr(feature = "tracing", track_caller)]
+#[inline(always)]
+pub(crate) fn created<T: ?Sized>(p: NonNull<T>) {
+    event("create", p);
+}
+
+#[cfg_attr(feature = "tracing", track_caller)]
+#[inline(always)]
+pub(crate) fn copied<T: ?Sized>(p: NonNull<T>) {
+    event("copy", p);
+}
+
+#[cfg_attr(feature = "tracing", track_caller)]
+#[inline(always)]
+pub(crate) fn deleted<T: ?Sized>(p: NonNull<T>) {
+    event("delete", p);
+}
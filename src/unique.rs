@@ -0,0 +1,244 @@
+use crate::AliasPtr;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// An alias which has exclusive access to its target, for as long as it exists.
+///
+/// Traits like `Future` need `&mut` access to the value, which an `AliasPtr`
+/// can't provide safely, since other aliases may be reading the target at the same time.
+/// Creating a `UniqueAlias` is `unsafe`, and asserts that no other alias will access
+/// the target until it is dropped. In exchange, it implements `DerefMut`,
+/// and forwards `Iterator`, `Future` (and `Stream`, with the `futures` feature)
+/// to its target like `Box`.
+///
+/// Dropping a `UniqueAlias` does not delete the target.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::{AliasPtr, UniqueAlias};
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// let fut = AliasPtr::new(async { 42 });
+/// {
+///     // Safety: no other alias accesses the future until `pinned` is dropped,
+///     // and the future is only dropped in place, by `delete()`.
+///     let mut pinned = pin!(unsafe { UniqueAlias::into_pin(UniqueAlias::new(fut.copy())) });
+///     let mut cx = Context::from_waker(Waker::noop());
+///     assert_eq!(pinned.as_mut().poll(&mut cx), Poll::Ready(42));
+/// }
+/// // `pinned` was dropped, so the future may be deleted.
+/// unsafe { fut.delete(); }
+/// ```
+pub struct UniqueAlias<T: ?Sized>(AliasPtr<T>);
+
+impl<T: ?Sized> UniqueAlias<T> {
+    /// Asserts that `ptr` has exclusive access to its target.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`with_mut`][AliasPtr::with_mut] apply,
+    /// until the `UniqueAlias` is dropped: the target must not be deleted,
+    /// and no other references to it may be live or created through other aliases.
+    /// (Concurrently polling a future through multiple aliases is undefined behavior.)
+    pub unsafe fn new(ptr: AliasPtr<T>) -> UniqueAlias<T> {
+        UniqueAlias(ptr)
+    }
+
+    /// Returns the alias, giving up exclusive access.
+    pub fn into_inner(this: Self) -> AliasPtr<T> {
+        this.0
+    }
+
+    /// Pins the target, allowing `!Unpin` futures to be polled.
+    ///
+    /// The target is stored on the heap, so moving the returned `Pin` does not move it.
+    ///
+    /// # Safety
+    ///
+    /// As with [`into_pin_box`][AliasPtr::into_pin_box], the target must not be
+    /// moved out of its allocation (for example through `into_box()` or `read()`)
+    /// until it is dropped in place, even after the `UniqueAlias` is gone.
+    pub unsafe fn into_pin(this: Self) -> Pin<UniqueAlias<T>> {
+        // Safety: the caller of `new()` ensured nobody else accesses the target,
+        // and our caller ensured it never moves out of its allocation.
+        Pin::new_unchecked(this)
+    }
+}
+
+impl<T: ?Sized> Deref for UniqueAlias<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> DerefMut for UniqueAlias<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: the caller of `new()` ensured this alias has exclusive access.
+        unsafe { &mut *AliasPtr::as_mut_ptr(&self.0) }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for UniqueAlias<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Advances the target, like `Box<I>`.
+impl<I: ?Sized + Iterator> Iterator for UniqueAlias<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        (**self).next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<I::Item> {
+        (**self).nth(n)
+    }
+}
+
+impl<I: ?Sized + DoubleEndedIterator> DoubleEndedIterator for UniqueAlias<I> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        (**self).next_back()
+    }
+}
+
+impl<I: ?Sized + ExactSizeIterator> ExactSizeIterator for UniqueAlias<I> {}
+
+/// Polls the target, like `Box<F>`. To poll `!Unpin` futures,
+/// pin the `UniqueAlias` with [`into_pin`][UniqueAlias::into_pin].
+impl<F: ?Sized + Future + Unpin> Future for UniqueAlias<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        F::poll(Pin::new(&mut **self.get_mut()), cx)
+    }
+}
+
+/// Polls the target for its next item, like `Box<S>`. To poll `!Unpin` streams,
+/// pin the `UniqueAlias` with [`into_pin`][UniqueAlias::into_pin].
+///
+/// As with `Future`, only one alias may poll the stream at a time,
+/// which the caller of [`new`][UniqueAlias::new] guarantees.
+#[cfg(feature = "futures")]
+impl<S: ?Sized + futures_core::Stream + Unpin> futures_core::Stream for UniqueAlias<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        S::poll_next(Pin::new(&mut **self.get_mut()), cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::task::Waker;
+
+    /// Returns `Pending` until polled `n` times.
+    struct Countdown(u32);
+
+    impl Future for Countdown {
+        type Output = &'static str;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<&'static str> {
+            if self.0 == 0 {
+                Poll::Ready("done")
+            } else {
+                self.0 -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn test_iterator() {
+        let range = AliasPtr::from(Box::new(0..6));
+        let mut unique = unsafe { UniqueAlias::new(range.copy()) };
+        assert_eq!(unique.len(), 6);
+        assert_eq!(unique.next(), Some(0));
+        assert_eq!(unique.next_back(), Some(5));
+        assert_eq!(unique.by_ref().step_by(2).collect::<Vec<_>>(), [1, 3]);
+
+        // Give up exclusive access before reading through the other alias.
+        let _ = UniqueAlias::into_inner(unique);
+        // Advancing through the UniqueAlias advanced the shared target.
+        assert!(range.is_empty());
+        unsafe {
+            range.delete();
+        }
+    }
+
+    #[test]
+    fn test_poll_unpin() {
+        let fut = AliasPtr::new(Countdown(2));
+        let mut unique = unsafe { UniqueAlias::new(fut.copy()) };
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(Pin::new(&mut unique).poll(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut unique).poll(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut unique).poll(&mut cx), Poll::Ready("done"));
+
+        let fut = UniqueAlias::into_inner(unique);
+        assert_eq!((*fut).0, 0);
+        unsafe {
+            fut.delete();
+        }
+    }
+
+    #[test]
+    fn test_poll_pinned() {
+        let fut = AliasPtr::new(async {
+            Countdown(3).await;
+            let inner = AliasPtr::new(Countdown(1));
+            let out = unsafe { UniqueAlias::new(inner.copy()) }.await;
+            unsafe {
+                inner.delete();
+            }
+            out
+        });
+        let mut polls = 1;
+        {
+            let mut pinned = pin!(unsafe { UniqueAlias::into_pin(UniqueAlias::new(fut.copy())) });
+            let mut cx = Context::from_waker(Waker::noop());
+            while pinned.as_mut().poll(&mut cx).is_pending() {
+                polls += 1;
+            }
+        }
+        assert_eq!(polls, 5);
+        unsafe {
+            fut.delete();
+        }
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_stream() {
+        use futures::executor::block_on;
+        use futures::stream::{self, StreamExt};
+
+        let inner = AliasPtr::new(stream::iter(1..=4));
+        let unique = unsafe { UniqueAlias::new(inner.copy()) };
+        let items: Vec<i32> = block_on(unique.map(|x| x * 10).collect());
+        assert_eq!(items, [10, 20, 30, 40]);
+        unsafe {
+            inner.delete();
+        }
+    }
+}
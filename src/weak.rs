@@ -0,0 +1,268 @@
+use crate::{Alias, AliasPtr};
+use std::error::Error;
+use std::fmt;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A heap-allocated `T` which can hand out weak handles,
+/// which detect when it has been dropped.
+///
+/// Each `AliasOwner` shares a liveness flag with its [`AliasWeak`] handles,
+/// which is cleared when the owner is dropped, mirroring `Rc::downgrade()`.
+/// Unlike the `checked` feature, this is always enabled, and only costs
+/// a reference-counted flag per owner rather than per `AliasPtr`.
+/// Since a weak handle does not keep the owner alive,
+/// [`upgrade`][AliasWeak::upgrade] is `unsafe`.
+///
+/// # Examples
+///
+/// ```
+/// # use alias_ptr::AliasOwner;
+/// let owner = AliasOwner::new(5);
+/// let weak = AliasOwner::downgrade(&owner);
+/// assert_eq!(unsafe { *weak.upgrade().unwrap() }, 5);
+///
+/// drop(owner);
+/// assert!(!weak.is_alive());
+/// assert!(unsafe { weak.upgrade() }.is_none());
+/// ```
+pub struct AliasOwner<T: ?Sized> {
+    ptr: AliasPtr<T>,
+    alive: Arc<AtomicBool>,
+}
+
+/// A weak handle to the target of an [`AliasOwner`],
+/// which can be upgraded to an alias while the owner is alive.
+pub struct AliasWeak<T: ?Sized> {
+    ptr: AliasPtr<T>,
+    alive: Arc<AtomicBool>,
+}
+
+impl<T> AliasOwner<T> {
+    /// Allocates memory on the heap and then places `x` into it.
+    pub fn new(x: T) -> AliasOwner<T> {
+        AliasOwner {
+            ptr: AliasPtr::new(x),
+            alive: Arc::new(AtomicBool::new(true)),
+        }
+    }
+}
+
+/// Takes ownership of a boxed value, which allows owning unsized types
+/// like `dyn Error`.
+impl<T: ?Sized> From<Box<T>> for AliasOwner<T> {
+    fn from(b: Box<T>) -> Self {
+        AliasOwner {
+            ptr: AliasPtr::from(b),
+            alive: Arc::new(AtomicBool::new(true)),
+        }
+    }
+}
+
+impl<T: ?Sized> AliasOwner<T> {
+    /// Creates a weak handle to the target.
+    pub fn downgrade(this: &Self) -> AliasWeak<T> {
+        AliasWeak {
+            ptr: this.ptr.copy(),
+            alive: this.alive.clone(),
+        }
+    }
+
+    /// Creates an alias to the target, which borrows the `AliasOwner`.
+    ///
+    /// An alias converted by [`Alias::into_ptr`] must not be dereferenced
+    /// after the `AliasOwner` is dropped, and must not be deleted.
+    pub fn alias(this: &Self) -> Alias<'_, T> {
+        // Safety: the target is only deleted when the owner is dropped,
+        // and is never mutably borrowed.
+        unsafe { Alias::new(this.ptr.copy()) }
+    }
+
+    /// Leaks the target, without running its destructor or freeing it.
+    ///
+    /// Unlike [`alias`][AliasOwner::alias], the target outlives the owner, so weak handles
+    /// remain upgradable and aliases remain valid for the rest of the program.
+    /// Unlike [`AliasPtr::into_raw_with_layout`], no pointer is returned,
+    /// so the memory can never be reclaimed.
+    pub fn forget(this: Self) {
+        let mut this = ManuallyDrop::new(this);
+        // Safety: `this` is never dropped, so its fields are only dropped once.
+        // Dropping the pointer (unlike deleting it) only releases its liveness
+        // tracking with the `checked` feature, and the flag stays set,
+        // since the target is never freed.
+        unsafe {
+            std::ptr::drop_in_place(&mut this.ptr);
+            std::ptr::drop_in_place(&mut this.alive);
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for AliasOwner<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.ptr
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for AliasOwner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for AliasOwner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Error> Error for AliasOwner<T> {
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    #[allow(deprecated)]
+    fn cause(&self) -> Option<&dyn Error> {
+        (**self).cause()
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        (**self).source()
+    }
+}
+
+/// Traces the target's `Gc` pointers, like `Box`.
+#[cfg(feature = "gc-arena")]
+unsafe impl<'gc, T: ?Sized + gc_arena::Collect<'gc>> gc_arena::Collect<'gc> for AliasOwner<T> {
+    const NEEDS_TRACE: bool = T::NEEDS_TRACE;
+
+    fn trace<C: gc_arena::collect::Trace<'gc>>(&self, cc: &mut C) {
+        cc.trace(&**self);
+    }
+}
+
+impl<T: ?Sized> Drop for AliasOwner<T> {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Release);
+        // Safety: `ptr` was created by AliasPtr::new(),
+        // and weak handles check the flag before handing out aliases.
+        unsafe {
+            self.ptr.copy().delete();
+        }
+    }
+}
+
+impl<T: ?Sized> AliasWeak<T> {
+    /// Returns `true` if the owner has not been dropped.
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Acquire)
+    }
+
+    /// Returns an alias to the target, or `None` if the owner has been dropped.
+    ///
+    /// # Safety
+    ///
+    /// A weak handle does not keep the owner alive, so the owner may be dropped
+    /// at any point after this returns, even on another thread.
+    /// The alias must not be dereferenced after the `AliasOwner` is dropped,
+    /// and must not be deleted.
+    pub unsafe fn upgrade(&self) -> Option<AliasPtr<T>> {
+        self.is_alive().then(|| self.ptr.copy())
+    }
+}
+
+impl<T: ?Sized> Clone for AliasWeak<T> {
+    fn clone(&self) -> Self {
+        AliasWeak {
+            ptr: self.ptr.copy(),
+            alive: self.alive.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for AliasWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(AliasWeak)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_upgrade() {
+        let owner = AliasOwner::new(vec![1, 2]);
+        let weak = AliasOwner::downgrade(&owner);
+        let weak2 = weak.clone();
+        assert!(weak.is_alive());
+        unsafe {
+            assert_eq!(*weak.upgrade().unwrap(), [1, 2]);
+            assert_eq!(weak2.upgrade().unwrap().len(), 2);
+        }
+
+        drop(owner);
+        assert!(!weak.is_alive());
+        assert!(unsafe { weak.upgrade() }.is_none());
+        assert!(unsafe { weak2.upgrade() }.is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let owner = AliasOwner::new(String::from("owned"));
+        assert_eq!(format!("{}", owner), "owned");
+        assert_eq!(format!("[{:>7}]", owner), "[  owned]");
+    }
+
+    #[test]
+    fn test_error() {
+        fn parse(s: &str) -> Result<u32, AliasOwner<std::num::ParseIntError>> {
+            s.parse().map_err(AliasOwner::new)
+        }
+
+        // `?` boxes the owner, which frees the error when the box is dropped.
+        fn report(s: &str) -> Result<u32, Box<dyn Error>> {
+            Ok(parse(s)?)
+        }
+
+        let err = report("x").unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit found in string");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn test_drop() {
+        let counter = Rc::new(());
+        let weak = {
+            let owner = AliasOwner::new(counter.clone());
+            let alias = AliasOwner::alias(&owner);
+            assert!(Rc::ptr_eq(&alias, &counter));
+            AliasOwner::downgrade(&owner)
+        };
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert!(unsafe { weak.upgrade() }.is_none());
+    }
+
+    #[test]
+    fn test_forget() {
+        let counter = Rc::new(());
+        let owner = AliasOwner::new(counter.clone());
+        let weak = AliasOwner::downgrade(&owner);
+        AliasOwner::forget(owner);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        assert!(weak.is_alive());
+
+        // Reclaim the leak, so Miri's leak checker passes.
+        let alias = unsafe { weak.upgrade() }.unwrap();
+        drop(weak);
+        unsafe {
+            alias.delete();
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
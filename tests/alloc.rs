@@ -0,0 +1,245 @@
+//! Checks how `AliasPtr` interacts with the global allocator,
+//! by counting allocations made on the current thread,
+//! and by making them fail on demand to exercise fallible constructors.
+//!
+//! The `checked` feature allocates a liveness flag for each `AliasPtr`,
+//! so these counts only hold without it.
+#![cfg(not(feature = "checked"))]
+
+use alias_ptr::AliasPtr;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+    static DEALLOCS: Cell<usize> = const { Cell::new(0) };
+    static ALLOC_BYTES: Cell<usize> = const { Cell::new(0) };
+    static DEALLOC_BYTES: Cell<usize> = const { Cell::new(0) };
+    static LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+    static REMAINING: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() > LIMIT.try_with(Cell::get).unwrap_or(usize::MAX) {
+            return std::ptr::null_mut();
+        }
+        let remaining = REMAINING.try_with(Cell::get).unwrap_or(usize::MAX);
+        if remaining == 0 {
+            return std::ptr::null_mut();
+        }
+        let _ = REMAINING.try_with(|n| n.set(remaining.saturating_sub(1)));
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        let _ = ALLOC_BYTES.try_with(|n| n.set(n.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = DEALLOCS.try_with(|n| n.set(n.get() + 1));
+        let _ = DEALLOC_BYTES.try_with(|n| n.set(n.get() + layout.size()));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the number of (allocations, deallocations) made on this thread while running `f`.
+fn count<R>(f: impl FnOnce() -> R) -> (usize, usize) {
+    let allocs = ALLOCS.with(Cell::get);
+    let deallocs = DEALLOCS.with(Cell::get);
+    drop(f());
+    (
+        ALLOCS.with(Cell::get) - allocs,
+        DEALLOCS.with(Cell::get) - deallocs,
+    )
+}
+
+/// Returns the number of bytes (allocated, deallocated) on this thread while running `f`.
+fn count_bytes<R>(f: impl FnOnce() -> R) -> (usize, usize) {
+    let allocs = ALLOC_BYTES.with(Cell::get);
+    let deallocs = DEALLOC_BYTES.with(Cell::get);
+    drop(f());
+    (
+        ALLOC_BYTES.with(Cell::get) - allocs,
+        DEALLOC_BYTES.with(Cell::get) - deallocs,
+    )
+}
+
+/// Runs `f` with allocations on this thread larger than `limit` bytes failing.
+fn with_limit<R>(limit: usize, f: impl FnOnce() -> R) -> R {
+    let old = LIMIT.with(|l| l.replace(limit));
+    let out = f();
+    LIMIT.with(|l| l.set(old));
+    out
+}
+
+/// Runs `f` with allocations on this thread failing after the first `n` succeed.
+fn with_fail_after<R>(n: usize, f: impl FnOnce() -> R) -> R {
+    let old = REMAINING.with(|r| r.replace(n));
+    let out = f();
+    REMAINING.with(|r| r.set(old));
+    out
+}
+
+#[test]
+fn zst_does_not_allocate() {
+    struct ZeroSized;
+
+    assert!(AliasPtr::<()>::is_zst());
+    assert!(AliasPtr::<ZeroSized>::is_zst());
+    assert!(!AliasPtr::<u8>::is_zst());
+
+    let counts = count(|| {
+        let x = AliasPtr::new(());
+        let y = x.copy();
+        assert_eq!(*y, ());
+        unsafe {
+            x.delete();
+        }
+
+        let z = AliasPtr::new(ZeroSized);
+        unsafe {
+            z.delete();
+        }
+    });
+    assert_eq!(counts, (0, 0));
+}
+
+#[test]
+fn sized_allocates_once() {
+    let counts = count(|| {
+        let x = AliasPtr::new(1u64);
+        let y = x.copy();
+        unsafe {
+            y.delete();
+        }
+    });
+    assert_eq!(counts, (1, 1));
+}
+
+#[test]
+fn try_new_uninit_slice_fails_gracefully() {
+    with_limit(1024, || {
+        assert!(AliasPtr::<[u8]>::try_new_uninit_slice(4096).is_err());
+
+        let small = AliasPtr::<[u8]>::try_new_uninit_slice(512).unwrap();
+        assert_eq!(small.len(), 512);
+        unsafe {
+            small.delete();
+        }
+    });
+}
+
+#[test]
+fn try_new_uninit_slice_first_allocation_fails() {
+    let counts = count(|| {
+        with_fail_after(0, || {
+            assert!(AliasPtr::<[u32]>::try_new_uninit_slice(16).is_err());
+            // Empty slices don't allocate, so they still succeed.
+            let empty = AliasPtr::<[u32]>::try_new_uninit_slice(0).unwrap();
+            unsafe {
+                empty.delete();
+            }
+        })
+    });
+    assert_eq!(counts, (0, 0));
+}
+
+#[test]
+fn try_new_uninit_slice_later_allocation_fails() {
+    let counts = count(|| {
+        with_fail_after(2, || {
+            let a = AliasPtr::<[u8]>::try_new_uninit_slice(8).unwrap();
+            let b = AliasPtr::<[u8]>::try_new_uninit_slice(8).unwrap();
+            assert!(AliasPtr::<[u8]>::try_new_uninit_slice(8).is_err());
+            unsafe {
+                a.delete();
+                b.delete();
+            }
+        })
+    });
+    // The failed allocation left nothing behind to free.
+    assert_eq!(counts, (2, 2));
+}
+
+#[test]
+fn try_new_uninit_slice_recovers_after_failure() {
+    let counts = count(|| {
+        let result = with_fail_after(0, || AliasPtr::<[u64]>::try_new_uninit_slice(4));
+        assert!(result.is_err());
+        // Once allocations succeed again, the same request does too.
+        let x = AliasPtr::<[u64]>::try_new_uninit_slice(4).unwrap();
+        let x = AliasPtr::write_slice(x, &[1, 2, 3, 4]);
+        assert_eq!(*x, [1, 2, 3, 4]);
+        unsafe {
+            x.delete();
+        }
+    });
+    assert_eq!(counts, (1, 1));
+}
+
+#[test]
+fn frees_through_global_allocator() {
+    use std::fmt::Display;
+
+    let run = || {
+        let x = AliasPtr::new([0u8; 24]);
+        let y = x.copy();
+        unsafe {
+            y.delete();
+        }
+
+        let slice: AliasPtr<[u32]> = AliasPtr::from(vec![1, 2, 3].into_boxed_slice());
+        drop(unsafe { AliasPtr::into_box(slice) });
+
+        let dyn_ptr: AliasPtr<dyn Display> = AliasPtr::from(Box::new(5u64) as Box<dyn Display>);
+        unsafe {
+            dyn_ptr.delete();
+        }
+
+        let raw = Box::into_raw(Box::new(1u16));
+        unsafe {
+            AliasPtr::from_raw(raw).delete();
+        }
+    };
+    assert_eq!(count(run), (4, 4));
+    // Every allocation was freed with the layout it was allocated with.
+    assert_eq!(count_bytes(run), (24 + 12 + 8 + 2, 24 + 12 + 8 + 2));
+}
+
+#[test]
+fn vec_conversion_is_tight() {
+    let mut vec = Vec::<u32>::with_capacity(64);
+    vec.extend([1, 2, 3]);
+    // Converting shrinks the allocation, so deleting frees exactly 3 elements.
+    let bytes = count_bytes(|| {
+        let x: AliasPtr<[u32]> = vec.into();
+        assert_eq!(*x, [1, 2, 3]);
+        unsafe {
+            x.delete();
+        }
+    });
+    assert_eq!(bytes, (12, 64 * 4 + 12));
+}
+
+#[test]
+fn try_new_uninit_slice_allocates_exactly() {
+    // One allocation of exactly 5 elements, with no shrinking reallocation.
+    let counts = count(|| {
+        let bytes = count_bytes(|| {
+            let x = AliasPtr::<[u16]>::try_new_uninit_slice(5).unwrap();
+            assert_eq!(x.len(), 5);
+            unsafe {
+                x.delete();
+            }
+        });
+        assert_eq!(bytes, (10, 10));
+    });
+    assert_eq!(counts, (1, 1));
+
+    let err = AliasPtr::<[u64]>::try_new_uninit_slice(usize::MAX).unwrap_err();
+    assert_eq!(err.to_string(), "memory allocation failed");
+}
@@ -0,0 +1,34 @@
+//! Checks the `Send`/`Sync` bounds described in the `AliasPtr` docs:
+//! `AliasPtr<T>` is `Send` and `Sync` only if `T` is both `Send` and `Sync`,
+//! like `Arc<T>`.
+
+use alias_ptr::{AliasFamily, AliasPtr, AliasVec};
+use static_assertions::{assert_impl_all, assert_not_impl_any};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::{Mutex, MutexGuard};
+
+// Send + Sync targets.
+assert_impl_all!(AliasPtr<i32>: Send, Sync);
+assert_impl_all!(AliasPtr<str>: Send, Sync);
+assert_impl_all!(AliasPtr<[String]>: Send, Sync);
+assert_impl_all!(AliasPtr<Mutex<Vec<i32>>>: Send, Sync);
+assert_impl_all!(AliasPtr<dyn Fn() + Send + Sync>: Send, Sync);
+
+// Send but not Sync: sharing across threads is disallowed,
+// and so is sending, since another thread could delete the target.
+assert_not_impl_any!(AliasPtr<Cell<i32>>: Send, Sync);
+assert_not_impl_any!(AliasPtr<dyn Fn() + Send>: Send, Sync);
+
+// Sync but not Send: the target must not be dropped on another thread.
+assert_not_impl_any!(AliasPtr<MutexGuard<'static, i32>>: Send, Sync);
+
+// Neither.
+assert_not_impl_any!(AliasPtr<Rc<i32>>: Send, Sync);
+assert_not_impl_any!(AliasPtr<*const i32>: Send, Sync);
+
+// Containers inherit the bounds of AliasPtr.
+assert_impl_all!(AliasFamily<Mutex<i32>>: Send, Sync);
+assert_not_impl_any!(AliasFamily<Cell<i32>>: Send, Sync);
+assert_impl_all!(AliasVec<String>: Send, Sync);
+assert_not_impl_any!(AliasVec<Rc<i32>>: Send, Sync);
@@ -0,0 +1,59 @@
+//! Checks that aliased errors work as sources in `anyhow` error chains.
+
+use alias_ptr::{AliasOwner, AliasPtr};
+use static_assertions::assert_impl_all;
+use std::error::Error;
+use std::{fmt, io};
+
+type DynError = dyn Error + Send + Sync;
+
+assert_impl_all!(AliasPtr<DynError>: Error, Send, Sync);
+assert_impl_all!(AliasOwner<DynError>: Error, Send, Sync);
+
+#[derive(Debug)]
+struct ReadError(io::Error);
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("read failed")
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn aliased_error_in_anyhow_chain() {
+    let boxed: Box<DynError> = Box::new(ReadError(io::Error::new(
+        io::ErrorKind::NotFound,
+        "config.toml",
+    )));
+    let owner = AliasOwner::from(boxed);
+    let weak = AliasOwner::downgrade(&owner);
+
+    let err = anyhow::Error::new(owner).context("loading config");
+    let chain: Vec<String> = err.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain, ["loading config", "read failed", "config.toml"]);
+    let io_err = err.chain().last().unwrap().downcast_ref::<io::Error>();
+    assert_eq!(io_err.unwrap().kind(), io::ErrorKind::NotFound);
+
+    // Aliases remain valid while the `anyhow::Error` owns the error.
+    let alias = unsafe { weak.upgrade() }.unwrap();
+    assert_eq!(alias.to_string(), "read failed");
+
+    drop(err);
+    assert!(unsafe { weak.upgrade() }.is_none());
+}
+
+#[test]
+fn aliased_error_sent_across_threads() {
+    let owner: AliasOwner<DynError> =
+        AliasOwner::from(Box::new(ReadError(io::Error::other("disk"))) as Box<DynError>);
+    let err = std::thread::spawn(move || anyhow::Error::new(owner))
+        .join()
+        .unwrap();
+    assert_eq!(err.root_cause().to_string(), "disk");
+}
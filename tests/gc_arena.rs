@@ -0,0 +1,74 @@
+//! Checks that `gc-arena` traces `Gc` pointers reachable through aliases.
+#![cfg(feature = "gc-arena")]
+
+use alias_ptr::{Alias, AliasOwner, AliasPtr};
+use gc_arena::collect::Trace;
+use gc_arena::{Arena, Collect, Gc, GcWeak, Rootable};
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Node<'gc> {
+    child: Gc<'gc, i32>,
+}
+
+/// Holds an owner it doesn't trace, plus an alias it does,
+/// so the node's child is only reachable through the alias.
+struct AliasRoot<'gc> {
+    owner: AliasOwner<Node<'gc>>,
+    alias: AliasPtr<Node<'gc>>,
+    reachable: GcWeak<'gc, i32>,
+    unreachable: GcWeak<'gc, i32>,
+}
+
+unsafe impl<'gc> Collect<'gc> for AliasRoot<'gc> {
+    fn trace<C: Trace<'gc>>(&self, cc: &mut C) {
+        cc.trace(&self.alias);
+        cc.trace(&self.reachable);
+        cc.trace(&self.unreachable);
+    }
+}
+
+#[test]
+fn traces_through_alias() {
+    let mut arena = Arena::<Rootable![AliasRoot<'_>]>::new(|mc| {
+        let child = Gc::new(mc, 42);
+        let owner = AliasOwner::new(Node { child });
+        // Safety: the alias is stored next to its owner, and never deleted.
+        let alias = unsafe { Alias::into_ptr(AliasOwner::alias(&owner)) };
+        AliasRoot {
+            owner,
+            alias,
+            reachable: Gc::downgrade(child),
+            unreachable: Gc::downgrade(Gc::new(mc, 0)),
+        }
+    });
+    arena.finish_cycle();
+    arena.mutate(|mc, root| {
+        assert!(root.unreachable.upgrade(mc).is_none());
+        let child = root.reachable.upgrade(mc).unwrap();
+        assert_eq!(*child, 42);
+        assert!(Gc::ptr_eq(child, root.owner.child));
+    });
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct OwnerRoot<'gc> {
+    owner: AliasOwner<Node<'gc>>,
+    reachable: GcWeak<'gc, i32>,
+}
+
+#[test]
+fn traces_through_owner() {
+    let mut arena = Arena::<Rootable![OwnerRoot<'_>]>::new(|mc| {
+        let child = Gc::new(mc, 7);
+        OwnerRoot {
+            owner: AliasOwner::new(Node { child }),
+            reachable: Gc::downgrade(child),
+        }
+    });
+    arena.finish_cycle();
+    arena.mutate(|mc, root| {
+        assert_eq!(*root.reachable.upgrade(mc).unwrap(), 7);
+    });
+}
@@ -0,0 +1,74 @@
+//! Checks that every constructor's allocation is freed by the matching destructor.
+//!
+//! These tests only detect leaks under Miri, which fails a test run
+//! if any memory is still allocated (and not reachable from a static) on exit:
+//!
+//! ```text
+//! cargo +nightly miri test --target-dir miri --test leaks
+//! ```
+
+use alias_ptr::{alias_dyn, AliasFamily, AliasPtr, AliasVec, ArenaSlice};
+use std::fmt::Display;
+
+#[test]
+fn delete_new() {
+    let x = AliasPtr::new(String::from("hello"));
+    let y = x.copy();
+    assert_eq!(*y, "hello");
+    unsafe {
+        x.delete();
+    }
+}
+
+#[test]
+fn delete_from_box() {
+    let x = AliasPtr::from(Box::new(vec![1, 2, 3]));
+    unsafe {
+        x.delete();
+    }
+}
+
+#[test]
+fn into_box() {
+    let x = AliasPtr::new(String::from("hello"));
+    let b = unsafe { AliasPtr::into_box(x) };
+    assert_eq!(*b, "hello");
+}
+
+#[test]
+fn try_into_inner() {
+    let x = AliasPtr::new(String::from("hello"));
+    let s = unsafe { AliasPtr::try_into_inner(x) }.unwrap();
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn delete_slice() {
+    let x: AliasPtr<[String]> = [String::from("a"), String::from("b")].into();
+    let elems = AliasPtr::element_aliases(&x);
+    assert_eq!(*elems[1], "b");
+    unsafe {
+        x.delete();
+    }
+}
+
+#[test]
+fn delete_dyn() {
+    let x: AliasPtr<dyn Display> = alias_dyn!(Display, AliasPtr::new(String::from("a")));
+    assert_eq!(x.to_string(), "a");
+    unsafe {
+        x.delete();
+    }
+}
+
+#[test]
+fn drop_containers() {
+    let family = AliasFamily::new(String::from("a"));
+    let _alias = family.alias();
+
+    let mut vec = AliasVec::with_capacity(2);
+    vec.push(String::from("a")).unwrap();
+
+    let mut arena = ArenaSlice::new(2);
+    arena.init(1, String::from("b"));
+}
@@ -0,0 +1,50 @@
+//! Checks that the owning containers can be used without writing any `unsafe`,
+//! for crates which forbid it.
+//!
+//! This is only sound because the containers hand out `Alias` handles
+//! borrowing the container, rather than `AliasPtr`s which could outlive it.
+
+#![forbid(unsafe_code)]
+
+use alias_ptr::{Alias, AliasFamily, AliasVec, ArenaSlice};
+use std::cell::Cell;
+
+#[test]
+fn family() {
+    let family = AliasFamily::new(Cell::new(0));
+    for _ in 0..3 {
+        let alias = family.alias();
+        alias.set(alias.get() + 1);
+    }
+    assert_eq!(family.get().get(), 3);
+}
+
+#[test]
+fn vec() {
+    let mut vec = AliasVec::with_capacity(2);
+    vec.push(String::from("a")).unwrap();
+    vec.push(String::from("b")).unwrap();
+    let a = vec.alias(0).unwrap();
+    assert_eq!(*a, "a");
+    assert_eq!(vec.get(1).unwrap(), "b");
+}
+
+#[test]
+fn arena() {
+    let mut arena = ArenaSlice::new(2);
+    arena.init(1, String::from("b"));
+    let b = arena.alias(1).unwrap();
+    assert_eq!(*b, "b");
+    assert!(arena.alias(0).is_none());
+}
+
+#[test]
+fn aliases_borrow_owner() {
+    let family = AliasFamily::new(String::from("a"));
+    let alias = family.alias();
+    // Cloning a handle copies the alias, but keeps borrowing the family.
+    let handles: Vec<_> = (0..3).map(|_| alias.clone()).collect();
+    assert!(handles
+        .iter()
+        .all(|h| std::ptr::eq(Alias::as_ptr(h), family.get())));
+}
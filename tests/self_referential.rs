@@ -0,0 +1,109 @@
+//! Worked examples of self-referential structs holding aliases to their own data.
+//!
+//! If the aliased data lives in its own heap allocation, the struct can move freely,
+//! since moving an `AliasPtr` never moves its target.
+//! If the aliased data is stored inline in the struct, the struct must be pinned
+//! before creating aliases, and the aliases must be discarded before it is dropped.
+
+use alias_ptr::AliasPtr;
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+/// Owns a heap-allocated counter, and holds aliases to it.
+struct HeapCounter {
+    owner: AliasPtr<Cell<u32>>,
+    aliases: Vec<AliasPtr<Cell<u32>>>,
+}
+
+impl HeapCounter {
+    fn new() -> HeapCounter {
+        let owner = AliasPtr::new(Cell::new(0));
+        let aliases = vec![owner.copy(), owner.copy()];
+        HeapCounter { owner, aliases }
+    }
+
+    fn bump_all(&self) {
+        for alias in &self.aliases {
+            alias.set(alias.get() + 1);
+        }
+    }
+}
+
+impl Drop for HeapCounter {
+    fn drop(&mut self) {
+        self.aliases.clear();
+        // Safety: `owner` was created by AliasPtr::new(), and no aliases remain.
+        unsafe {
+            self.owner.copy().delete();
+        }
+    }
+}
+
+#[test]
+fn heap_owner_moves_freely() {
+    let counter = HeapCounter::new();
+    counter.bump_all();
+
+    // Moving the struct (here, into and out of a Vec) leaves the aliases valid.
+    let mut moved = vec![counter];
+    let counter = moved.pop().unwrap();
+    counter.bump_all();
+    assert_eq!(counter.owner.get(), 4);
+}
+
+/// Stores a counter inline, and holds an alias to it once pinned.
+struct InlineCounter {
+    value: Cell<u32>,
+    alias: Option<AliasPtr<Cell<u32>>>,
+    _pinned: PhantomPinned,
+}
+
+impl InlineCounter {
+    fn new() -> InlineCounter {
+        InlineCounter {
+            value: Cell::new(0),
+            alias: None,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Establishes the alias. Must be called after pinning,
+    /// since the alias points into `self`.
+    fn init(self: Pin<&mut Self>) {
+        // Safety: we don't move out of `this`.
+        let this = unsafe { self.get_unchecked_mut() };
+        // Safety: the struct is pinned, so `value` does not move until it is dropped,
+        // and the alias (stored in the same struct) can't outlive it.
+        // The alias is never deleted, since `value` is not heap-allocated.
+        this.alias = Some(unsafe { AliasPtr::from_ptr(&this.value) });
+    }
+
+    fn bump(self: Pin<&Self>) {
+        let alias = self.get_ref().alias.as_ref().expect("not initialized");
+        alias.set(alias.get() + 1);
+    }
+}
+
+#[test]
+fn inline_owner_pinned() {
+    let mut counter = Box::pin(InlineCounter::new());
+    counter.as_mut().init();
+    counter.as_ref().bump();
+
+    // Moving the pinned box moves the pointer, not the struct.
+    let mut moved = vec![counter];
+    let counter = moved.pop().unwrap();
+    counter.as_ref().bump();
+    assert_eq!(counter.value.get(), 2);
+}
+
+#[test]
+fn inline_owner_pinned_on_stack() {
+    let mut counter = std::pin::pin!(InlineCounter::new());
+    counter.as_mut().init();
+    for _ in 0..3 {
+        counter.as_ref().bump();
+    }
+    assert_eq!(counter.value.get(), 3);
+}
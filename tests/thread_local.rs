@@ -0,0 +1,66 @@
+//! Worked example of per-thread singletons: a `thread_local!` owns a value
+//! for the lifetime of its thread, and hands out aliases to code on that thread.
+//!
+//! The value holds a `Cell`, so it is not `Sync`, and aliases to it are not `Send`.
+//! This keeps aliases from escaping to other threads, which could outlive the owner.
+
+use alias_ptr::AliasPtr;
+use std::cell::Cell;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+struct Cache {
+    hits: Cell<u32>,
+}
+
+struct Owner(AliasPtr<Cache>);
+
+impl Drop for Owner {
+    fn drop(&mut self) {
+        // Safety: aliases are only handed out on this thread,
+        // and not used in other thread-local destructors.
+        unsafe {
+            self.0.copy().delete();
+        }
+    }
+}
+
+thread_local! {
+    static CACHE: Owner = Owner(AliasPtr::new(Cache { hits: Cell::new(0) }));
+}
+
+/// Returns an alias to this thread's cache.
+fn cache() -> AliasPtr<Cache> {
+    CACHE.with(|owner| owner.0.copy())
+}
+
+#[test]
+fn per_thread_caches() {
+    const THREADS: u32 = 4;
+    // Keeps every thread (and its cache) alive until all have recorded their address.
+    let barrier = Arc::new(Barrier::new(THREADS as usize));
+    let threads: Vec<_> = (1..=THREADS)
+        .map(|n| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let a = cache();
+                let b = cache();
+                for _ in 0..n {
+                    a.hits.set(a.hits.get() + 1);
+                }
+                assert_eq!(b.hits.get(), n);
+                let addr = AliasPtr::as_ptr(&a) as usize;
+                barrier.wait();
+                addr
+            })
+        })
+        .collect();
+
+    let mut addrs: Vec<usize> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    addrs.sort_unstable();
+    addrs.dedup();
+    assert_eq!(addrs.len(), THREADS as usize);
+
+    // The main thread's cache is independent of the others.
+    assert_eq!(cache().hits.get(), 0);
+}
@@ -0,0 +1,76 @@
+//! Checks the events emitted with the `tracing` feature enabled.
+#![cfg(feature = "tracing")]
+
+use alias_ptr::AliasPtr;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records the message, address, and location of each event, formatted as strings.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<[String; 3]>>>);
+
+#[derive(Default)]
+struct Fields([String; 3]);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let i = match field.name() {
+            "message" => 0,
+            "addr" => 1,
+            "location" => 2,
+            _ => return,
+        };
+        self.0[i] = format!("{:?}", value);
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "alias_ptr"
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn lifecycle_events() {
+    let recorder = Recorder::default();
+    let line = line!() + 2;
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let x = AliasPtr::new(1);
+        let y = x.copy();
+        unsafe {
+            y.delete();
+        }
+    });
+
+    let events = recorder.0.lock().unwrap();
+    let messages: Vec<&str> = events.iter().map(|e| &*e[0]).collect();
+    assert_eq!(messages, ["create", "copy", "delete"]);
+
+    let addr = &events[0][1];
+    assert!(events.iter().all(|e| e[1] == *addr));
+    for (event, line) in events.iter().zip([line, line + 1, line + 3]) {
+        let location = format!("{}:{}:", file!(), line);
+        assert!(event[2].starts_with(&location), "{:?}", event);
+    }
+}